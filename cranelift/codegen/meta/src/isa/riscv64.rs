@@ -154,6 +154,20 @@ pub(crate) fn define() -> TargetIsa {
         false,
     );
 
+    let _has_ztso = setting.add_bool(
+        "has_ztso",
+        "has extension ztso?",
+        "Ztso: Total Store Ordering",
+        false,
+    );
+
+    let _has_zacas = setting.add_bool(
+        "has_zacas",
+        "has extension zacas?",
+        "Zacas: Atomic Compare-and-Swap Instructions",
+        false,
+    );
+
     let has_zicsr = setting.add_bool(
         "has_zicsr",
         "has extension zicsr?",
@@ -167,6 +181,13 @@ pub(crate) fn define() -> TargetIsa {
         true,
     );
 
+    let _emulate_unaligned = setting.add_bool(
+        "emulate_unaligned",
+        "Lower unaligned loads and stores to byte accesses?",
+        "Split loads and stores not marked as aligned into byte-sized accesses, for cores where misaligned accesses trap or are slow",
+        false,
+    );
+
     // Zvl*: Minimum Vector Length Standard Extensions
     // These extension specify the minimum number of bits in a vector register.
     // Since it is a minimum, Zvl64b implies Zvl32b, Zvl128b implies Zvl64b, etc.
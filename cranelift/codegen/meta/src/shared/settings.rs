@@ -167,7 +167,7 @@ pub(crate) fn define() -> SettingGroup {
         "tls_model",
         "Defines the model used to perform TLS accesses.",
         "",
-        vec!["none", "elf_gd", "macho", "coff"],
+        vec!["none", "elf_gd", "macho", "coff", "elf_ie", "elf_le"],
     );
 
     settings.add_enum(
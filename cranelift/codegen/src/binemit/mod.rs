@@ -109,12 +109,42 @@ pub enum Reloc {
     /// <https://github.com/riscv-non-isa/riscv-elf-psabi-doc/blob/master/riscv-elf.adoc#pc-relative-symbol-addresses>
     RiscvPCRelLo12I,
 
+    /// High 20 bits of a 32-bit PC-relative offset relocation
+    ///
+    /// This is the `R_RISCV_PCREL_HI20` relocation from the RISC-V ELF psABI document.
+    /// <https://github.com/riscv-non-isa/riscv-elf-psabi-doc/blob/master/riscv-elf.adoc#pc-relative-symbol-addresses>
+    RiscvPCRelHi20,
+
     /// High 20 bits of a 32-bit PC-relative GOT offset relocation
     ///
     /// This is the `R_RISCV_GOT_HI20` relocation from the RISC-V ELF psABI document.
     /// <https://github.com/riscv-non-isa/riscv-elf-psabi-doc/blob/master/riscv-elf.adoc#pc-relative-symbol-addresses>
     RiscvGotHi20,
 
+    /// RISC-V TLS IE: High 20 bits of 32-bit PC-relative TLS IE GOT reference
+    ///
+    /// This is the `R_RISCV_TLS_GOT_HI20` relocation from the RISC-V ELF psABI document.
+    /// <https://github.com/riscv-non-isa/riscv-elf-psabi-doc/blob/master/riscv-elf.adoc#initial-exec>
+    RiscvTlsGotHi20,
+
+    /// RISC-V TLS LE: High 20 bits of the thread pointer offset
+    ///
+    /// This is the `R_RISCV_TPREL_HI20` relocation from the RISC-V ELF psABI document.
+    /// <https://github.com/riscv-non-isa/riscv-elf-psabi-doc/blob/master/riscv-elf.adoc#local-exec>
+    RiscvTPRelHi20,
+
+    /// RISC-V TLS LE: Marks the `add` of the thread pointer, for linker relaxation
+    ///
+    /// This is the `R_RISCV_TPREL_ADD` relocation from the RISC-V ELF psABI document.
+    /// <https://github.com/riscv-non-isa/riscv-elf-psabi-doc/blob/master/riscv-elf.adoc#local-exec>
+    RiscvTPRelAdd,
+
+    /// RISC-V TLS LE: Low 12 bits of the thread pointer offset (I-Type instruction)
+    ///
+    /// This is the `R_RISCV_TPREL_LO12_I` relocation from the RISC-V ELF psABI document.
+    /// <https://github.com/riscv-non-isa/riscv-elf-psabi-doc/blob/master/riscv-elf.adoc#local-exec>
+    RiscvTPRelLo12I,
+
     /// s390x TLS GD64 - 64-bit offset of tls_index for GD symbol in GOT
     S390xTlsGd64,
     /// s390x TLS GDCall - marker to enable optimization of TLS calls
@@ -145,6 +175,11 @@ impl fmt::Display for Reloc {
             Self::RiscvTlsGdHi20 => write!(f, "RiscvTlsGdHi20"),
             Self::RiscvGotHi20 => write!(f, "RiscvGotHi20"),
             Self::RiscvPCRelLo12I => write!(f, "RiscvPCRelLo12I"),
+            Self::RiscvPCRelHi20 => write!(f, "RiscvPCRelHi20"),
+            Self::RiscvTlsGotHi20 => write!(f, "RiscvTlsGotHi20"),
+            Self::RiscvTPRelHi20 => write!(f, "RiscvTPRelHi20"),
+            Self::RiscvTPRelAdd => write!(f, "RiscvTPRelAdd"),
+            Self::RiscvTPRelLo12I => write!(f, "RiscvTPRelLo12I"),
             Self::ElfX86_64TlsGd => write!(f, "ElfX86_64TlsGd"),
             Self::MachOX86_64Tlv => write!(f, "MachOX86_64Tlv"),
             Self::MachOAarch64TlsAdrPage21 => write!(f, "MachOAarch64TlsAdrPage21"),
@@ -1086,7 +1086,11 @@ impl ABIMachineSpec for AArch64MachineDeps {
         }
     }
 
-    fn get_machine_env(flags: &settings::Flags, _call_conv: isa::CallConv) -> &MachineEnv {
+    fn get_machine_env(
+        flags: &settings::Flags,
+        _isa_flags: &Self::F,
+        _call_conv: isa::CallConv,
+    ) -> &'static MachineEnv {
         if flags.enable_pinned_reg() {
             static MACHINE_ENV: OnceLock<MachineEnv> = OnceLock::new();
             MACHINE_ENV.get_or_init(|| create_reg_env(true))
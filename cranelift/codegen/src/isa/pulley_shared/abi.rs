@@ -474,7 +474,11 @@ where
         }
     }
 
-    fn get_machine_env(_flags: &settings::Flags, _call_conv: isa::CallConv) -> &MachineEnv {
+    fn get_machine_env(
+        _flags: &settings::Flags,
+        _isa_flags: &PulleyFlags,
+        _call_conv: isa::CallConv,
+    ) -> &'static MachineEnv {
         static MACHINE_ENV: OnceLock<MachineEnv> = OnceLock::new();
         MACHINE_ENV.get_or_init(create_reg_environment)
     }
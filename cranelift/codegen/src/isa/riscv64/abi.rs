@@ -30,6 +30,11 @@ pub(crate) type Riscv64Callee = Callee<Riscv64MachineDeps>;
 /// point for the trait; it is never actually instantiated.
 pub struct Riscv64MachineDeps;
 
+/// Offset from the frame pointer at which `gen_prologue_frame_setup` saves
+/// the return address in the frame record. The caller's frame pointer is saved
+/// right below it, at offset 0.
+pub(crate) const FRAME_RECORD_RA_OFFSET: i64 = 8;
+
 impl IsaFlags for RiscvFlags {}
 
 impl RiscvFlags {
@@ -257,14 +262,19 @@ impl ABIMachineSpec for Riscv64MachineDeps {
                 imm12,
             });
         } else {
-            insts.extend(Inst::load_constant_u32(
-                writable_spilltmp_reg2(),
-                imm as u64,
-            ));
+            // Materialize the immediate in the destination unless that would
+            // clobber the source. This only runs in the prologue, so the
+            // fallback is a caller-saved register that isn't an argument.
+            let tmp = if into_reg.to_reg() != from_reg {
+                into_reg
+            } else {
+                Writable::from_reg(x_reg(28)) // t3
+            };
+            insts.extend(Inst::load_constant_u32(tmp, imm as u64));
             insts.push(Inst::AluRRR {
                 alu_op: AluOPRRR::Add,
                 rd: into_reg,
-                rs1: spilltmp_reg2(),
+                rs1: tmp.to_reg(),
                 rs2: from_reg,
             });
         }
@@ -342,7 +352,7 @@ impl ABIMachineSpec for Riscv64MachineDeps {
             // mv   fp,sp        ;; set fp to sp.
             insts.extend(Self::gen_sp_reg_adjust(-16));
             insts.push(Inst::gen_store(
-                AMode::SPOffset(8),
+                AMode::SPOffset(FRAME_RECORD_RA_OFFSET),
                 link_reg(),
                 I64,
                 MemFlags::trusted(),
@@ -382,7 +392,7 @@ impl ABIMachineSpec for Riscv64MachineDeps {
         if frame_layout.setup_area_size > 0 {
             insts.push(Inst::gen_load(
                 writable_link_reg(),
-                AMode::SPOffset(8),
+                AMode::SPOffset(FRAME_RECORD_RA_OFFSET),
                 I64,
                 MemFlags::trusted(),
             ));
@@ -396,9 +406,7 @@ impl ABIMachineSpec for Riscv64MachineDeps {
         }
 
         if call_conv == isa::CallConv::Tail && frame_layout.tail_args_size > 0 {
-            insts.extend(Self::gen_sp_reg_adjust(
-                frame_layout.tail_args_size.try_into().unwrap(),
-            ));
+            push_sp_adjust(&mut insts, frame_layout.tail_args_size.try_into().unwrap());
         }
 
         insts
@@ -414,14 +422,15 @@ impl ABIMachineSpec for Riscv64MachineDeps {
 
     fn gen_probestack(insts: &mut SmallInstVec<Self::I>, frame_size: u32) {
         insts.extend(Inst::load_constant_u32(writable_a0(), frame_size as u64));
-        let mut info = CallInfo::empty(
-            ExternalName::LibCall(LibCall::Probestack),
+        let info = Self::libcall_info(
             CallConv::SystemV,
+            LibCall::Probestack,
+            smallvec![CallArgPair {
+                vreg: a0(),
+                preg: a0(),
+            }],
+            smallvec![],
         );
-        info.uses.push(CallArgPair {
-            vreg: a0(),
-            preg: a0(),
-        });
         insts.push(Inst::Call {
             info: Box::new(info),
         });
@@ -444,7 +453,7 @@ impl ABIMachineSpec for Riscv64MachineDeps {
                 // Write the lr position on the stack again, as it hasn't changed since it was
                 // pushed in `gen_prologue_frame_setup`
                 insts.push(Inst::gen_store(
-                    AMode::SPOffset(8),
+                    AMode::SPOffset(FRAME_RECORD_RA_OFFSET),
                     link_reg(),
                     I64,
                     MemFlags::trusted(),
@@ -480,40 +489,29 @@ impl ABIMachineSpec for Riscv64MachineDeps {
 
         // Adjust the stack pointer downward for clobbers, the function fixed
         // frame (spillslots and storage slots), and outgoing arguments.
-        let stack_size = frame_layout.clobber_size
-            + frame_layout.fixed_frame_storage_size
-            + frame_layout.outgoing_args_size;
+        let stack_size = frame_stack_size(frame_layout);
 
         // Store each clobbered register in order at offsets from SP,
         // placing them above the fixed frame slots.
         if stack_size > 0 {
             insts.extend(Self::gen_sp_reg_adjust(-(stack_size as i32)));
 
-            let mut cur_offset = 8;
-            for reg in &frame_layout.clobbered_callee_saves {
-                let r_reg = reg.to_reg();
-                let ty = match r_reg.class() {
-                    RegClass::Int => I64,
-                    RegClass::Float => F64,
-                    RegClass::Vector => unimplemented!("Vector Clobber Saves"),
-                };
+            for slot in clobber_slots(frame_layout) {
                 insts.push(Inst::gen_store(
-                    AMode::SPOffset((stack_size - cur_offset) as i64),
-                    Reg::from(reg.to_reg()),
-                    ty,
+                    AMode::SPOffset(slot.sp_offset),
+                    Reg::from(slot.reg.to_reg()),
+                    slot.ty,
                     MemFlags::trusted(),
                 ));
 
                 if flags.unwind_info() {
                     insts.push(Inst::Unwind {
                         inst: UnwindInst::SaveReg {
-                            clobber_offset: frame_layout.clobber_size - cur_offset,
-                            reg: r_reg,
+                            clobber_offset: slot.clobber_offset,
+                            reg: slot.reg.to_reg(),
                         },
                     });
                 }
-
-                cur_offset += 8
             }
         }
         insts
@@ -526,25 +524,15 @@ impl ABIMachineSpec for Riscv64MachineDeps {
     ) -> SmallVec<[Inst; 16]> {
         let mut insts = SmallVec::new();
 
-        let stack_size = frame_layout.clobber_size
-            + frame_layout.fixed_frame_storage_size
-            + frame_layout.outgoing_args_size;
+        let stack_size = frame_stack_size(frame_layout);
 
-        let mut cur_offset = 8;
-        for reg in &frame_layout.clobbered_callee_saves {
-            let rreg = reg.to_reg();
-            let ty = match rreg.class() {
-                RegClass::Int => I64,
-                RegClass::Float => F64,
-                RegClass::Vector => unimplemented!("Vector Clobber Restores"),
-            };
+        for slot in clobber_slots(frame_layout) {
             insts.push(Inst::gen_load(
-                reg.map(Reg::from),
-                AMode::SPOffset(i64::from(stack_size - cur_offset)),
-                ty,
+                slot.reg.map(Reg::from),
+                AMode::SPOffset(slot.sp_offset),
+                slot.ty,
                 MemFlags::trusted(),
             ));
-            cur_offset += 8
         }
 
         if stack_size > 0 {
@@ -567,30 +555,32 @@ impl ABIMachineSpec for Riscv64MachineDeps {
         let arg2 = Writable::from_reg(x_reg(12));
         let tmp = alloc_tmp(Self::word_type());
         insts.extend(Inst::load_constant_u64(tmp, size as u64));
+        // `memcpy` returns its destination, which we have no use for.
+        let ret = alloc_tmp(Self::word_type());
+        let info = Self::libcall_info(
+            call_conv,
+            LibCall::Memcpy,
+            smallvec![
+                CallArgPair {
+                    vreg: dst,
+                    preg: arg0.to_reg()
+                },
+                CallArgPair {
+                    vreg: src,
+                    preg: arg1.to_reg()
+                },
+                CallArgPair {
+                    vreg: tmp.to_reg(),
+                    preg: arg2.to_reg()
+                }
+            ],
+            smallvec![CallRetPair {
+                vreg: ret,
+                location: RetLocation::Reg(arg0.to_reg(), Self::word_type()),
+            }],
+        );
         insts.push(Inst::Call {
-            info: Box::new(CallInfo {
-                dest: ExternalName::LibCall(LibCall::Memcpy),
-                uses: smallvec![
-                    CallArgPair {
-                        vreg: dst,
-                        preg: arg0.to_reg()
-                    },
-                    CallArgPair {
-                        vreg: src,
-                        preg: arg1.to_reg()
-                    },
-                    CallArgPair {
-                        vreg: tmp.to_reg(),
-                        preg: arg2.to_reg()
-                    }
-                ],
-                defs: smallvec![],
-                clobbers: Self::get_regs_clobbered_by_call(call_conv, false),
-                caller_conv: call_conv,
-                callee_conv: call_conv,
-                callee_pop_size: 0,
-                try_call_info: None,
-            }),
+            info: Box::new(info),
         });
         insts
     }
@@ -608,9 +598,18 @@ impl ABIMachineSpec for Riscv64MachineDeps {
         }
     }
 
-    fn get_machine_env(_flags: &settings::Flags, _call_conv: isa::CallConv) -> &MachineEnv {
-        static MACHINE_ENV: OnceLock<MachineEnv> = OnceLock::new();
-        MACHINE_ENV.get_or_init(create_reg_environment)
+    fn get_machine_env(
+        _flags: &settings::Flags,
+        isa_flags: &RiscvFlags,
+        _call_conv: isa::CallConv,
+    ) -> &'static MachineEnv {
+        if isa_flags.has_zca() {
+            static MACHINE_ENV: OnceLock<MachineEnv> = OnceLock::new();
+            MACHINE_ENV.get_or_init(|| create_reg_environment(true))
+        } else {
+            static MACHINE_ENV: OnceLock<MachineEnv> = OnceLock::new();
+            MACHINE_ENV.get_or_init(|| create_reg_environment(false))
+        }
     }
 
     fn get_regs_clobbered_by_call(
@@ -718,6 +717,21 @@ impl ABIMachineSpec for Riscv64MachineDeps {
             _ => &[],
         }
     }
+
+    fn is_leaf(f: &ir::Function) -> bool {
+        // 128-bit division and remainder have no native instruction and are
+        // lowered to a libcall instead.
+        let has_i128_div = f.layout.blocks().any(|block| {
+            f.layout.block_insts(block).any(|inst| {
+                matches!(
+                    f.dfg.insts[inst].opcode(),
+                    ir::Opcode::Udiv | ir::Opcode::Sdiv | ir::Opcode::Urem | ir::Opcode::Srem
+                ) && f.dfg.ctrl_typevar(inst) == I128
+            })
+        });
+
+        f.is_leaf() && !has_i128_div
+    }
 }
 
 // NOTE: no V regs are callee save.
@@ -738,6 +752,7 @@ const DEFAULT_CALLEE_SAVES: PRegSet = PRegSet::empty()
     .with(px_reg(27))
     // F Regs
     .with(pf_reg(8))
+    .with(pf_reg(9))
     .with(pf_reg(18))
     .with(pf_reg(19))
     .with(pf_reg(20))
@@ -749,6 +764,74 @@ const DEFAULT_CALLEE_SAVES: PRegSet = PRegSet::empty()
     .with(pf_reg(26))
     .with(pf_reg(27));
 
+/// Adjust SP by `amount` bytes at the end of `insts`.
+///
+/// If `insts` already ends with an `addi sp, sp, imm` and the combined
+/// amount still fits in its immediate, that instruction is updated in place
+/// instead, and dropped if the two adjustments cancel out.
+fn push_sp_adjust(insts: &mut SmallInstVec<Inst>, amount: i32) {
+    match insts.last_mut() {
+        Some(Inst::AluRRImm12 {
+            alu_op: AluOPRRI::Addi,
+            rd,
+            rs,
+            imm12,
+        }) if rd.to_reg() == stack_reg() && *rs == stack_reg() => {
+            match Imm12::maybe_from_i64(i64::from(imm12.as_i16()) + i64::from(amount)) {
+                Some(sum) if sum.as_i16() == 0 => {
+                    insts.pop();
+                }
+                Some(sum) => *imm12 = sum,
+                None => insts.extend(Riscv64MachineDeps::gen_sp_reg_adjust(amount)),
+            }
+        }
+        _ => insts.extend(Riscv64MachineDeps::gen_sp_reg_adjust(amount)),
+    }
+}
+
+/// The amount SP is lowered by after the frame setup: the clobbered
+/// callee-saves, the fixed frame (spillslots and storage slots) and the
+/// outgoing arguments.
+fn frame_stack_size(frame_layout: &FrameLayout) -> u32 {
+    frame_layout.clobber_size
+        + frame_layout.fixed_frame_storage_size
+        + frame_layout.outgoing_args_size
+}
+
+/// Where a clobbered callee-save lives while the function body runs.
+struct ClobberSlot {
+    reg: Writable<RealReg>,
+    ty: Type,
+    /// Offset from SP once the whole frame has been allocated.
+    sp_offset: i64,
+    /// Offset from the bottom of the clobber area, for unwind info.
+    clobber_offset: u32,
+}
+
+/// The save slots of `frame_layout.clobbered_callee_saves`, from the top of
+/// the clobber area down. Both the prologue and the epilogue walk this so that
+/// every register is restored from the slot it was saved to.
+fn clobber_slots(frame_layout: &FrameLayout) -> impl Iterator<Item = ClobberSlot> + '_ {
+    let stack_size = frame_stack_size(frame_layout);
+    frame_layout
+        .clobbered_callee_saves
+        .iter()
+        .zip((8..).step_by(8))
+        .map(move |(&reg, cur_offset)| {
+            let ty = match reg.to_reg().class() {
+                RegClass::Int => I64,
+                RegClass::Float => F64,
+                RegClass::Vector => unimplemented!("Vector Clobber Saves"),
+            };
+            ClobberSlot {
+                reg,
+                ty,
+                sp_offset: i64::from(stack_size - cur_offset),
+                clobber_offset: frame_layout.clobber_size - cur_offset,
+            }
+        })
+}
+
 fn compute_clobber_size(clobbers: &[Writable<RealReg>]) -> u32 {
     let mut clobbered_size = 0;
     for reg in clobbers {
@@ -791,7 +874,6 @@ const DEFAULT_CLOBBERS: PRegSet = PRegSet::empty()
     .with(pf_reg(5))
     .with(pf_reg(6))
     .with(pf_reg(7))
-    .with(pf_reg(9))
     .with(pf_reg(10))
     .with(pf_reg(11))
     .with(pf_reg(12))
@@ -936,66 +1018,105 @@ const ALL_CLOBBERS: PRegSet = PRegSet::empty()
     .with(pv_reg(30))
     .with(pv_reg(31));
 
-fn create_reg_environment() -> MachineEnv {
-    // Some C Extension instructions can only use a subset of the registers.
-    // x8 - x15, f8 - f15, v8 - v15 so we should prefer to use those since
-    // they allow us to emit C instructions more often.
+fn create_reg_environment(prefer_compressible: bool) -> MachineEnv {
+    // Values that don't live across a call are best kept in caller-saved
+    // registers, which cost nothing to use, while values that do can't be in
+    // them at all and end up in callee-saved ones. regalloc2 only falls back
+    // to the non-preferred registers once the preferred ones are taken, so
+    // every caller-saved register is preferred and the callee-saved ones,
+    // which have to be saved in the prologue, are not.
     //
-    // In general the order of preference is:
-    //   1. Compressible Caller Saved registers.
-    //   2. Non-Compressible Caller Saved registers.
-    //   3. Compressible Callee Saved registers.
-    //   4. Non-Compressible Callee Saved registers.
-
-    let preferred_regs_by_class: [Vec<PReg>; 3] = {
-        let x_registers: Vec<PReg> = (10..=15).map(px_reg).collect();
-        let f_registers: Vec<PReg> = (10..=15).map(pf_reg).collect();
-        let v_registers: Vec<PReg> = (8..=15).map(pv_reg).collect();
-
-        [x_registers, f_registers, v_registers]
+    // Most C Extension instructions can only use x8 - x15 and f8 - f15.
+    // regalloc2 starts its scan of each list at a different register for
+    // every bundle, so ordering a list barely matters. Instead, when
+    // compressed instructions are available, only the compressible
+    // caller-saved registers are preferred, and the other caller-saved ones
+    // move to the non-preferred list next to the callee-saved ones.
+
+    // x0 - x4 are special registers, so we don't want to use them.
+    // Omit x31 since it's the spilltmp register.
+    let x_caller_saved: Vec<PReg> = (10..=15)
+        .chain(5..=7)
+        .chain(16..=17)
+        .chain(28..=30)
+        .map(px_reg)
+        .collect();
+    let f_caller_saved: Vec<PReg> = (10..=15)
+        .chain(0..=7)
+        .chain(16..=17)
+        .chain(28..=31)
+        .map(pf_reg)
+        .collect();
+
+    // Omit x8 since it's the frame pointer. x9, f8 and f9 are callee saved,
+    // but compressible.
+    let x_callee_saved: Vec<PReg> = (9..=9).chain(18..=27).map(px_reg).collect();
+    let f_callee_saved: Vec<PReg> = (8..=9).chain(18..=27).map(pf_reg).collect();
+
+    let split = |caller_saved: Vec<PReg>, callee_saved: Vec<PReg>| {
+        if prefer_compressible {
+            let (compressible, rest): (Vec<PReg>, Vec<PReg>) = caller_saved
+                .into_iter()
+                .partition(|preg| (8..16).contains(&preg.hw_enc()));
+            (compressible, rest.into_iter().chain(callee_saved).collect())
+        } else {
+            (caller_saved, callee_saved)
+        }
     };
+    let (x_preferred, x_non_preferred) = split(x_caller_saved, x_callee_saved);
+    let (f_preferred, f_non_preferred) = split(f_caller_saved, f_callee_saved);
 
-    let non_preferred_regs_by_class: [Vec<PReg>; 3] = {
-        // x0 - x4 are special registers, so we don't want to use them.
-        // Omit x30 and x31 since they are the spilltmp registers.
-
-        // Start with the Non-Compressible Caller Saved registers.
-        let x_registers: Vec<PReg> = (5..=7)
-            .chain(16..=17)
-            .chain(28..=29)
-            // The first Callee Saved register is x9 since its Compressible
-            // Omit x8 since it's the frame pointer.
-            .chain(9..=9)
-            // The rest of the Callee Saved registers are Non-Compressible
-            .chain(18..=27)
-            .map(px_reg)
-            .collect();
-
-        // Prefer Caller Saved registers.
-        let f_registers: Vec<PReg> = (0..=7)
-            .chain(16..=17)
-            .chain(28..=31)
-            // Once those are exhausted, we should prefer f8 and f9 since they are
-            // callee saved, but compressible.
-            .chain(8..=9)
-            .chain(18..=27)
-            .map(pf_reg)
-            .collect();
-
-        let v_registers = (0..=7).chain(16..=31).map(pv_reg).collect();
-
-        [x_registers, f_registers, v_registers]
-    };
+    // No vector registers are callee saved.
+    let v_preferred = (8..=15).map(pv_reg).collect();
+    let v_non_preferred = (0..=7).chain(16..=31).map(pv_reg).collect();
 
     MachineEnv {
-        preferred_regs_by_class,
-        non_preferred_regs_by_class,
+        preferred_regs_by_class: [x_preferred, f_preferred, v_preferred],
+        non_preferred_regs_by_class: [x_non_preferred, f_non_preferred, v_non_preferred],
         fixed_stack_slots: vec![],
         scratch_by_class: [None, None, None],
     }
 }
 
 impl Riscv64MachineDeps {
+    /// Registers clobbered by a call to a libcall using `call_conv` which
+    /// returns its results in `rets`.
+    ///
+    /// Argument registers stay in the set: the callee is free to overwrite
+    /// them, so only the return registers are removed, as they are defined by
+    /// the call instead.
+    pub(crate) fn libcall_clobbers(
+        call_conv: isa::CallConv,
+        rets: impl IntoIterator<Item = PReg>,
+    ) -> PRegSet {
+        let mut clobbers = Self::get_regs_clobbered_by_call(call_conv, false);
+        for preg in rets {
+            clobbers.remove(preg);
+        }
+        clobbers
+    }
+
+    /// Builds the `CallInfo` for a call to `libcall` which is inserted by the
+    /// backend rather than coming from a call in the IR.
+    pub(crate) fn libcall_info(
+        call_conv: isa::CallConv,
+        libcall: LibCall,
+        uses: CallArgList,
+        defs: CallRetList,
+    ) -> CallInfo<ExternalName> {
+        let rets = defs.iter().filter_map(|def| match def.location {
+            RetLocation::Reg(reg, _) => Some(PReg::from(reg.to_real_reg().unwrap())),
+            RetLocation::Stack(..) => None,
+        });
+        let clobbers = Self::libcall_clobbers(call_conv, rets);
+        CallInfo {
+            uses,
+            defs,
+            clobbers,
+            ..CallInfo::empty(ExternalName::LibCall(libcall), call_conv)
+        }
+    }
+
     fn gen_probestack_unroll(
         insts: &mut SmallInstVec<Inst>,
         tmp: Writable<Reg>,
@@ -1035,3 +1156,335 @@ impl Riscv64MachineDeps {
         insts.extend(Self::gen_sp_reg_adjust((guard_size * probe_count) as i32));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cursor::{Cursor, FuncCursor};
+    use crate::dominator_tree::DominatorTree;
+    use crate::flowgraph::ControlFlowGraph;
+    use crate::ir::{
+        AbiParam, ExtFuncData, ExternalName, Function, InstBuilder, MemFlags, UserFuncName,
+    };
+    use crate::isa::riscv64::Riscv64Backend;
+    use crate::settings::Configurable;
+    use cranelift_control::ControlPlane;
+
+    #[test]
+    fn callee_saves_and_clobbers_are_complements() {
+        // x0 is hardwired to zero, and gp and tp belong to the platform.
+        let reserved = [px_reg(0), px_reg(3), px_reg(4)];
+        let all = (0..32)
+            .map(px_reg)
+            .chain((0..32).map(pf_reg))
+            .chain((0..32).map(pv_reg));
+        for preg in all {
+            let saved = DEFAULT_CALLEE_SAVES.contains(preg);
+            let clobbered = DEFAULT_CLOBBERS.contains(preg);
+            if reserved.contains(&preg) {
+                assert!(!saved && !clobbered, "{preg:?} is reserved");
+            } else {
+                assert!(
+                    saved != clobbered,
+                    "{preg:?} must be exactly one of saved or clobbered"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn callee_saves_are_not_preferred() {
+        for prefer_compressible in [false, true] {
+            let env = create_reg_environment(prefer_compressible);
+            for class in 0..2 {
+                for &preg in &env.preferred_regs_by_class[class] {
+                    assert!(DEFAULT_CLOBBERS.contains(preg), "{preg:?}");
+                }
+                if !prefer_compressible {
+                    for &preg in &env.non_preferred_regs_by_class[class] {
+                        assert!(DEFAULT_CALLEE_SAVES.contains(preg), "{preg:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn compressible_registers_preferred_with_c() {
+        let env = create_reg_environment(true);
+        let x: Vec<PReg> = (10..=15).map(px_reg).collect();
+        let f: Vec<PReg> = (10..=15).map(pf_reg).collect();
+        assert_eq!(env.preferred_regs_by_class[0], x);
+        assert_eq!(env.preferred_regs_by_class[1], f);
+
+        // The remaining caller-saved registers still come before the
+        // callee-saved ones.
+        for class in 0..2 {
+            let non_preferred = &env.non_preferred_regs_by_class[class];
+            let first_callee_save = non_preferred
+                .iter()
+                .position(|&preg| DEFAULT_CALLEE_SAVES.contains(preg))
+                .unwrap();
+            assert!(
+                non_preferred[first_callee_save..]
+                    .iter()
+                    .all(|&preg| DEFAULT_CALLEE_SAVES.contains(preg))
+            );
+        }
+    }
+
+    #[test]
+    fn allocatable_registers() {
+        // Everything but zero, ra, sp, gp, tp, the frame pointer and the
+        // spill temporary.
+        let mut expected = PRegSet::empty();
+        for preg in (5..=7)
+            .chain(9..=30)
+            .map(px_reg)
+            .chain((0..32).map(pf_reg))
+            .chain((0..32).map(pv_reg))
+        {
+            expected.add(preg);
+        }
+
+        for prefer_compressible in [false, true] {
+            let env = create_reg_environment(prefer_compressible);
+            let mut allocatable = PRegSet::empty();
+            for class in 0..3 {
+                for &preg in env.preferred_regs_by_class[class]
+                    .iter()
+                    .chain(&env.non_preferred_regs_by_class[class])
+                {
+                    assert!(!allocatable.contains(preg), "{preg:?} is listed twice");
+                    allocatable.add(preg);
+                }
+            }
+            assert_eq!(allocatable, expected);
+        }
+    }
+
+    /// Loads `n` values through the argument, combines neighbouring pairs and
+    /// stores the results back, so that all `n` results are live at once. If
+    /// `with_call` is set, the loaded values also have to survive a call.
+    fn create_pressure_function(n: i32, with_call: bool) -> Function {
+        let mut sig = Signature::new(CallConv::SystemV);
+        sig.params.push(AbiParam::new(I64));
+        sig.returns.push(AbiParam::new(I64));
+        let mut func = Function::with_name_signature(UserFuncName::user(0, 0), sig.clone());
+        let sig_ref = func.import_signature(sig);
+        let callee = func.import_function(ExtFuncData {
+            name: ExternalName::testcase("callee"),
+            signature: sig_ref,
+            colocated: true,
+        });
+
+        let block0 = func.dfg.make_block();
+        let ptr = func.dfg.append_block_param(block0, I64);
+        let mut pos = FuncCursor::new(&mut func);
+        pos.insert_block(block0);
+
+        let values: Vec<_> = (0..n)
+            .map(|i| pos.ins().load(I64, MemFlags::trusted(), ptr, i * 8))
+            .collect();
+        let ret = if with_call {
+            let call = pos.ins().call(callee, &[ptr]);
+            pos.func.dfg.first_result(call)
+        } else {
+            ptr
+        };
+        let results: Vec<_> = (0..n as usize)
+            .map(|i| {
+                let a = values[i];
+                let b = values[(i + 1) % n as usize];
+                let sum = pos.ins().iadd(a, b);
+                pos.ins().bxor(sum, a)
+            })
+            .collect();
+        for (i, &result) in results.iter().enumerate() {
+            pos.ins()
+                .store(MemFlags::trusted(), result, ptr, i as i32 * 8);
+        }
+        pos.ins().return_(&[ret]);
+
+        func
+    }
+
+    /// Compiles `func` with C and Zcb, allocating registers with the
+    /// environment given by `prefer_compressible`, and returns the size of
+    /// the emitted code.
+    fn emitted_size(func: &Function, prefer_compressible: bool) -> usize {
+        let mut isa_builder = crate::isa::riscv64::settings::builder();
+        isa_builder.enable("has_c").unwrap();
+        isa_builder.enable("has_zcb").unwrap();
+        let flags = settings::Flags::new(settings::builder());
+        let isa_flags = RiscvFlags::new(&flags, &isa_builder);
+        let backend =
+            Riscv64Backend::new_with_flags("riscv64".parse().unwrap(), flags.clone(), isa_flags);
+
+        let cfg = ControlFlowGraph::with_function(func);
+        let domtree = DominatorTree::with_function(func, &cfg);
+        let mut ctrl_plane = ControlPlane::default();
+        let (vcode, _) = backend
+            .compile_vcode(func, &domtree, &mut ctrl_plane)
+            .unwrap();
+        let env = create_reg_environment(prefer_compressible);
+        let regalloc =
+            regalloc2::run(&vcode, &env, &regalloc2::RegallocOptions::default()).unwrap();
+        vcode
+            .emit(&regalloc, false, &flags, &mut ctrl_plane)
+            .buffer
+            .data()
+            .len()
+    }
+
+    #[test]
+    fn preferring_compressible_registers_shrinks_code() {
+        // Functions with few live values shrink. Ones with more live values
+        // than there are compressible registers can grow, as they may now
+        // pick a callee-saved register that has to be saved in the prologue,
+        // but across the whole range the former wins.
+        let mut preferred = 0;
+        let mut not_preferred = 0;
+        for n in 2..=20 {
+            for with_call in [false, true] {
+                let func = create_pressure_function(n, with_call);
+                preferred += emitted_size(&func, true);
+                not_preferred += emitted_size(&func, false);
+            }
+        }
+        assert!(preferred < not_preferred, "{preferred} >= {not_preferred}");
+    }
+
+    #[test]
+    fn clobber_restore_matches_save() {
+        // An unsorted mix of int and float registers, including a caller-saved
+        // one that must not get a slot.
+        let regs: Vec<_> = [
+            pf_reg(27),
+            px_reg(9),
+            pf_reg(8),
+            px_reg(5),
+            px_reg(27),
+            pf_reg(9),
+            px_reg(18),
+        ]
+        .into_iter()
+        .map(|preg| Writable::from_reg(RealReg::from(preg)))
+        .collect();
+        let flags = settings::Flags::new(settings::builder());
+        assert!(flags.unwind_info());
+        let frame_layout = Riscv64MachineDeps::compute_frame_layout(
+            CallConv::SystemV,
+            &flags,
+            &Signature::new(CallConv::SystemV),
+            &regs,
+            false,
+            0,
+            0,
+            32,
+            48,
+            16,
+        );
+        assert_eq!(frame_layout.clobbered_callee_saves.len(), 6);
+
+        let saves: Vec<_> =
+            Riscv64MachineDeps::gen_clobber_save(CallConv::SystemV, &flags, &frame_layout)
+                .into_iter()
+                .filter_map(|inst| match inst {
+                    Inst::Store {
+                        to: AMode::SPOffset(offset),
+                        src,
+                        op,
+                        ..
+                    } => Some((src, offset, op.size())),
+                    _ => None,
+                })
+                .collect();
+        let restores: Vec<_> =
+            Riscv64MachineDeps::gen_clobber_restore(CallConv::SystemV, &flags, &frame_layout)
+                .into_iter()
+                .filter_map(|inst| match inst {
+                    Inst::Load {
+                        rd,
+                        from: AMode::SPOffset(offset),
+                        op,
+                        ..
+                    } => Some((rd.to_reg(), offset, op.size())),
+                    _ => None,
+                })
+                .collect();
+        assert_eq!(saves.len(), 6);
+        assert_eq!(saves, restores);
+
+        // Every slot is distinct and lies in the clobber area, right above
+        // the fixed frame and the outgoing arguments.
+        let mut offsets: Vec<_> = saves.iter().map(|&(_, offset, _)| offset).collect();
+        offsets.sort_unstable();
+        offsets.dedup();
+        assert_eq!(offsets.len(), saves.len());
+        assert!(offsets.iter().all(|&o| o >= 64 && o < 64 + 48));
+    }
+
+    fn call_info(insts: &[Inst]) -> &CallInfo<ExternalName> {
+        match insts.last() {
+            Some(Inst::Call { info }) => info,
+            other => panic!("expected a call, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn libcall_clobbers() {
+        let clobbers = Riscv64MachineDeps::libcall_clobbers(CallConv::SystemV, [px_reg(10)]);
+        let mut expected = DEFAULT_CLOBBERS;
+        expected.remove(px_reg(10));
+        assert_eq!(clobbers, expected);
+        // Arguments and other temporaries are still clobbered.
+        assert!(clobbers.contains(px_reg(11)));
+        assert!(clobbers.contains(px_reg(1)));
+        assert!(clobbers.contains(pf_reg(0)));
+        for preg in (0..32).map(px_reg).chain((0..32).map(pf_reg)) {
+            if DEFAULT_CALLEE_SAVES.contains(preg) {
+                assert!(!clobbers.contains(preg), "{preg:?} is callee-saved");
+            }
+        }
+    }
+
+    #[test]
+    fn memcpy_call_info() {
+        let mut tmps = [x_reg(5), x_reg(6)].into_iter();
+        let insts =
+            Riscv64MachineDeps::gen_memcpy(CallConv::SystemV, x_reg(18), x_reg(19), 64, |_| {
+                Writable::from_reg(tmps.next().unwrap())
+            });
+        let info = call_info(&insts);
+        let uses: Vec<_> = info.uses.iter().map(|u| (u.vreg, u.preg)).collect();
+        assert_eq!(
+            uses,
+            [
+                (x_reg(18), x_reg(10)),
+                (x_reg(19), x_reg(11)),
+                (x_reg(5), x_reg(12)),
+            ]
+        );
+        assert_eq!(info.defs.len(), 1);
+        assert!(matches!(
+            info.defs[0].location,
+            RetLocation::Reg(reg, I64) if reg == x_reg(10)
+        ));
+        assert_eq!(
+            info.clobbers,
+            Riscv64MachineDeps::libcall_clobbers(CallConv::SystemV, [px_reg(10)])
+        );
+    }
+
+    #[test]
+    fn probestack_call_info() {
+        let mut insts = SmallInstVec::new();
+        Riscv64MachineDeps::gen_probestack(&mut insts, 0x10000);
+        let info = call_info(&insts);
+        assert_eq!(info.uses.len(), 1);
+        assert_eq!(info.uses[0].preg, x_reg(10));
+        assert!(info.defs.is_empty());
+        assert_eq!(info.clobbers, DEFAULT_CLOBBERS);
+    }
+}
@@ -156,6 +156,19 @@ impl AMode {
         }
     }
 
+    /// Returns this addressing mode moved `amount` bytes further into memory.
+    pub(crate) fn offset_by(self, amount: i64) -> AMode {
+        match self {
+            AMode::RegOffset(reg, offset) => AMode::RegOffset(reg, offset + amount),
+            AMode::SPOffset(offset) => AMode::SPOffset(offset + amount),
+            AMode::FPOffset(offset) => AMode::FPOffset(offset + amount),
+            AMode::SlotOffset(offset) => AMode::SlotOffset(offset + amount),
+            // Incoming argument offsets are subtracted from the top of the frame.
+            AMode::IncomingArg(offset) => AMode::IncomingArg(offset - amount),
+            AMode::Const(_) | AMode::Label(_) => panic!("cannot offset {self}"),
+        }
+    }
+
     /// Retrieve a MachLabel that corresponds to this addressing mode, if it exists.
     pub(crate) fn get_label_with_sink(&self, sink: &mut MachBuffer<Inst>) -> Option<MachLabel> {
         match self {
@@ -1136,12 +1149,6 @@ impl AluOPRRI {
     }
 }
 
-impl Default for FRM {
-    fn default() -> Self {
-        Self::Fcsr
-    }
-}
-
 /// float rounding mode.
 impl FRM {
     pub(crate) fn to_static_str(self) -> &'static str {
@@ -1369,6 +1376,8 @@ impl AtomicOP {
             Self::AmomaxD => "amomax.d",
             Self::AmominuD => "amominu.d",
             Self::AmomaxuD => "amomaxu.d",
+            Self::AmocasW => "amocas.w",
+            Self::AmocasD => "amocas.d",
         };
         format!("{}{}", s, amo.to_static_str())
     }
@@ -1394,7 +1403,8 @@ impl AtomicOP {
             | AtomicOP::AmominW
             | AtomicOP::AmomaxW
             | AtomicOP::AmominuW
-            | AtomicOP::AmomaxuW => 0b010,
+            | AtomicOP::AmomaxuW
+            | AtomicOP::AmocasW => 0b010,
             AtomicOP::LrD
             | AtomicOP::ScD
             | AtomicOP::AmoswapD
@@ -1405,7 +1415,8 @@ impl AtomicOP {
             | AtomicOP::AmominD
             | AtomicOP::AmomaxD
             | AtomicOP::AmominuD
-            | AtomicOP::AmomaxuD => 0b011,
+            | AtomicOP::AmomaxuD
+            | AtomicOP::AmocasD => 0b011,
         }
     }
     pub(crate) fn funct5(self) -> u32 {
@@ -1432,6 +1443,8 @@ impl AtomicOP {
             AtomicOP::AmomaxD => 0b10100,
             AtomicOP::AmominuD => 0b11000,
             AtomicOP::AmomaxuD => 0b11100,
+            AtomicOP::AmocasW => 0b00101,
+            AtomicOP::AmocasD => 0b00101,
         }
     }
 
@@ -1581,6 +1594,42 @@ impl AMO {
     pub(crate) fn as_u32(self) -> u32 {
         self as u32
     }
+
+    /// Does this ordering need to order earlier memory accesses before the
+    /// atomic operation?
+    pub(crate) fn is_release(self) -> bool {
+        matches!(self, AMO::Release | AMO::SeqCst)
+    }
+
+    /// Does this ordering need to order later memory accesses after the
+    /// atomic operation?
+    pub(crate) fn is_acquire(self) -> bool {
+        matches!(self, AMO::Acquire | AMO::SeqCst)
+    }
+
+    /// The ordering bits for the `lr` of an LR/SC loop with this ordering.
+    ///
+    /// The ISA manual recommends against `lr.rl` without `aq`, so the release
+    /// half of the ordering is carried by the `sc` alone.
+    pub(crate) fn load_reserved(self) -> AMO {
+        match self {
+            AMO::Relax | AMO::Release => AMO::Relax,
+            AMO::Acquire => AMO::Acquire,
+            AMO::SeqCst => AMO::SeqCst,
+        }
+    }
+
+    /// The ordering bits for the `sc` of an LR/SC loop with this ordering.
+    ///
+    /// Similarly `sc.aq` without `rl` is discouraged, so the acquire half of
+    /// the ordering is carried by the `lr` alone.
+    pub(crate) fn store_conditional(self) -> AMO {
+        match self {
+            AMO::Relax | AMO::Acquire => AMO::Relax,
+            AMO::Release => AMO::Release,
+            AMO::SeqCst => AMO::SeqCst,
+        }
+    }
 }
 
 impl Inst {
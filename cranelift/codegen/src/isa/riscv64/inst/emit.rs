@@ -170,8 +170,11 @@ impl Inst {
             | Inst::ReturnCallInd { .. }
             | Inst::Jal { .. }
             | Inst::CondBr { .. }
+            | Inst::CondBrOr { .. }
             | Inst::LoadExtName { .. }
             | Inst::ElfTlsGetAddr { .. }
+            | Inst::ElfTlsInitialExec { .. }
+            | Inst::ElfTlsLocalExec { .. }
             | Inst::LoadAddr { .. }
             | Inst::Mov { .. }
             | Inst::MovFromPReg { .. }
@@ -182,6 +185,7 @@ impl Inst {
             | Inst::FpuRRRR { .. }
             | Inst::Jalr { .. }
             | Inst::Atomic { .. }
+            | Inst::Amocas { .. }
             | Inst::Select { .. }
             | Inst::AtomicCas { .. }
             | Inst::RawData { .. }
@@ -191,8 +195,6 @@ impl Inst {
             | Inst::TrapIf { .. }
             | Inst::Unwind { .. }
             | Inst::DummyUse { .. }
-            | Inst::Popcnt { .. }
-            | Inst::Cltz { .. }
             | Inst::Brev8 { .. }
             | Inst::StackProbeLoop { .. } => None,
 
@@ -217,6 +219,33 @@ impl Inst {
             Inst::EmitIsland { .. } => None,
         }
     }
+
+    /// Returns true if this instruction only copies a register onto itself.
+    ///
+    /// These show up after register allocation assigns the source and the
+    /// destination of a copy to the same register and can be dropped without
+    /// changing anything. Writes to `zero` are left alone since that's how
+    /// `Nop4` is emitted, and `fsgnj` is only an exact copy when it operates
+    /// on the whole register.
+    fn is_identity_move(&self) -> bool {
+        match *self {
+            Inst::AluRRImm12 {
+                alu_op: AluOPRRI::Addi | AluOPRRI::Ori | AluOPRRI::Xori,
+                rd,
+                rs,
+                imm12,
+            } => rd.to_reg() == rs && rs != zero_reg() && imm12.bits() == 0,
+            Inst::FpuRRR {
+                alu_op: FpuOPRRR::Fsgnj,
+                width: FpuOPWidth::D,
+                rd,
+                rs1,
+                rs2,
+                ..
+            } => rd.to_reg() == rs1 && rs1 == rs2,
+            _ => false,
+        }
+    }
 }
 
 impl MachInstEmit for Inst {
@@ -224,6 +253,10 @@ impl MachInstEmit for Inst {
     type Info = EmitInfo;
 
     fn emit(&self, sink: &mut MachBuffer<Inst>, emit_info: &Self::Info, state: &mut EmitState) {
+        if self.is_identity_move() {
+            return;
+        }
+
         // Check if we need to update the vector state before emitting this instruction
         if let Some(expected) = self.expected_vstate() {
             if state.vstate != EmitVState::Known(*expected) {
@@ -876,7 +909,7 @@ impl Inst {
                 // use to load some data and rely on some position in the code stream.
                 // and we may exceed `Inst::worst_case_size`.
                 // for more information see https://github.com/bytecodealliance/wasmtime/pull/5612.
-                sink.put_data(&data[..]);
+                sink.put_inline_data(&data[..]);
             }
             &Inst::Lui { rd, ref imm } => {
                 let x: u32 = 0b0110111 | reg_to_gpr_num(rd.to_reg()) << 7 | (imm.bits() << 12);
@@ -1003,19 +1036,22 @@ impl Inst {
                 }
                 .emit(sink, emit_info, state);
                 // NaN-box the `f16` before loading it into the floating-point
-                // register with a 32-bit `fmv`.
-                Inst::Lui {
-                    rd: writable_spilltmp_reg2(),
-                    imm: Imm20::from_i32((0xffff_0000_u32 as i32) >> 12),
-                }
-                .emit(sink, emit_info, state);
-                Inst::AluRRR {
-                    alu_op: AluOPRRR::Or,
-                    rd: writable_spilltmp_reg(),
-                    rs1: spilltmp_reg(),
-                    rs2: spilltmp_reg2(),
+                // register with a 32-bit `fmv`. Setting the upper bits to ones
+                // is done as `!zext16(!x)` so that no second scratch register
+                // is needed for the mask.
+                Inst::construct_bit_not(writable_spilltmp_reg(), spilltmp_reg())
+                    .emit(sink, emit_info, state);
+                for alu_op in [AluOPRRI::Slli, AluOPRRI::Srli] {
+                    Inst::AluRRImm12 {
+                        alu_op,
+                        rd: writable_spilltmp_reg(),
+                        rs: spilltmp_reg(),
+                        imm12: Imm12::from_i16(48),
+                    }
+                    .emit(sink, emit_info, state);
                 }
-                .emit(sink, emit_info, state);
+                Inst::construct_bit_not(writable_spilltmp_reg(), spilltmp_reg())
+                    .emit(sink, emit_info, state);
                 Inst::FpuRR {
                     alu_op: FpuOPRR::FmvFmtX,
                     width: FpuOPWidth::S,
@@ -1325,6 +1361,27 @@ impl Inst {
                     CondBrTarget::Fallthrough => {}
                 };
             }
+            &Inst::CondBrOr {
+                taken,
+                not_taken,
+                kind1,
+                kind2,
+            } => {
+                // Both conditional branches are enrolled in the branch
+                // simplification of `MachBuffer`, like a lone `CondBr`.
+                Inst::CondBr {
+                    taken,
+                    not_taken: CondBrTarget::Fallthrough,
+                    kind: kind1,
+                }
+                .emit(sink, emit_info, state);
+                Inst::CondBr {
+                    taken,
+                    not_taken,
+                    kind: kind2,
+                }
+                .emit(sink, emit_info, state);
+            }
 
             &Inst::Mov { rd, rm, ty } => {
                 debug_assert_eq!(rd.to_reg().class(), rm.class());
@@ -1380,13 +1437,26 @@ impl Inst {
                 let default_target = targets[0];
                 let targets = &targets[1..];
 
-                // We are going to potentially emit a large amount of instructions, so ensure that we emit an island
+                // Jump tables can be disabled to avoid indirect jumps on an index that may be
+                // attacker controlled. In that case, and for tables so small that a single
+                // comparison picks the target, we dispatch through a tree of branches instead.
+                let use_jump_table = emit_info.shared_flag.enable_jump_tables()
+                    && targets.len() > MAX_BR_TABLE_TREE_TARGETS;
+
+                // We are going to potentially emit a large amount of code, so ensure that we emit an island
                 // now if we need one.
                 //
-                // The worse case PC calculations are 12 instructions. And each entry in the jump table is 2 instructions.
-                // Check if we need to emit a jump table here to support that jump.
-                let inst_count = 12 + (targets.len() * 2);
-                let distance = (inst_count * Inst::UNCOMPRESSED_INSTRUCTION_SIZE as usize) as u32;
+                // The bounds check is up to 7 instructions. The worse case PC calculations add another
+                // 7 instructions, the last one being up to 2 bytes of padding that align the table.
+                // Each entry in the jump table is a 4 byte offset. A branch tree is made of nodes of
+                // up to 5 instructions and leaves of 2 instructions.
+                let entries = u32::try_from(targets.len()).unwrap();
+                let distance = if use_jump_table {
+                    14 * Inst::UNCOMPRESSED_INSTRUCTION_SIZE as u32 + 4 * entries
+                } else {
+                    (7 + 5 * entries.saturating_sub(1) + 2 * entries)
+                        * Inst::UNCOMPRESSED_INSTRUCTION_SIZE as u32
+                };
                 if sink.island_needed(distance) {
                     let jump_around_label = sink.get_label();
                     Inst::gen_jump(jump_around_label).emit(sink, emit_info, state);
@@ -1395,9 +1465,9 @@ impl Inst {
                 }
 
                 // We emit a bounds check on the index, if the index is larger than the number of
-                // jump table entries, we jump to the default block.  Otherwise we compute a jump
-                // offset by multiplying the index by 8 (the size of each entry) and then jump to
-                // that offset. Each jump table entry is a regular auipc+jalr which we emit sequentially.
+                // jump table entries, we jump to the default block. Otherwise we load the offset
+                // of the target block from the jump table, which follows the dispatch sequence,
+                // add it to the address of the table and jump there.
                 //
                 // Build the following sequence:
                 //
@@ -1410,14 +1480,15 @@ impl Inst {
                 //     auipc   pc, 0
                 //     jalr    zero, pc, default_block
                 // compute_target:
-                //     auipc   pc, 0
-                //     slli    tmp, ext_index, 3
-                //     add     pc, pc, tmp
-                //     jalr    zero, pc, 0x10
+                //     auipc   base, 0
+                //     slli    tmp, ext_index, 2
+                //     add     tmp, base, tmp
+                //     lw      tmp, table_offset(tmp)
+                //     add     base, base, tmp
+                //     jalr    zero, base, table_offset
                 // jump_table:
-                //     ; This repeats for each entry in the jumptable
-                //     auipc   pc, 0
-                //     jalr    zero, pc, block_target
+                //     ; Aligned to 4 bytes, this repeats for each entry in the jumptable
+                //     .4byte  block_target - jump_table
 
                 // Extend the index to 64 bits.
                 //
@@ -1460,57 +1531,95 @@ impl Inst {
                 // We need to emit a PC relative offset,
                 sink.bind_label(label_compute_target, &mut state.ctrl_plane);
 
-                // Get the current PC.
-                Inst::Auipc {
-                    rd: tmp1,
-                    imm: Imm20::ZERO,
-                }
-                .emit_uncompressed(sink, emit_info, state, start_off);
-
-                // These instructions must be emitted as uncompressed since we
-                // are manually computing the offset from the PC.
+                if use_jump_table {
+                    // The table starts right after the 6 instructions of the dispatch
+                    // sequence, plus any padding needed to align it. Since compressed
+                    // instructions may precede us, the `auipc` is only 2 byte aligned.
+                    let auipc_offset = sink.cur_offset();
+                    let table_offset = (auipc_offset
+                        + 6 * Inst::UNCOMPRESSED_INSTRUCTION_SIZE as u32)
+                        .next_multiple_of(4)
+                        - auipc_offset;
+                    let table_offset_imm = Imm12::from_i16(table_offset as i16);
 
-                // Multiply the index by 8, since that is the size in
-                // bytes of each jump table entry
-                Inst::AluRRImm12 {
-                    alu_op: AluOPRRI::Slli,
-                    rd: tmp2,
-                    rs: ext_index.to_reg(),
-                    imm12: Imm12::from_i16(3),
-                }
-                .emit_uncompressed(sink, emit_info, state, start_off);
+                    // Get the current PC.
+                    Inst::Auipc {
+                        rd: tmp1,
+                        imm: Imm20::ZERO,
+                    }
+                    .emit_uncompressed(sink, emit_info, state, start_off);
 
-                // Calculate the base of the jump, PC + the offset from above.
-                Inst::AluRRR {
-                    alu_op: AluOPRRR::Add,
-                    rd: tmp1,
-                    rs1: tmp1.to_reg(),
-                    rs2: tmp2.to_reg(),
-                }
-                .emit_uncompressed(sink, emit_info, state, start_off);
+                    // These instructions must be emitted as uncompressed since we
+                    // are manually computing the offset from the PC.
 
-                // Jump to the middle of the jump table.
-                // We add a 16 byte offset here, since we used 4 instructions
-                // since the AUIPC that was used to get the PC.
-                Inst::Jalr {
-                    rd: writable_zero_reg(),
-                    base: tmp1.to_reg(),
-                    offset: Imm12::from_i16((4 * Inst::UNCOMPRESSED_INSTRUCTION_SIZE) as i16),
-                }
-                .emit_uncompressed(sink, emit_info, state, start_off);
+                    // Multiply the index by 4, since that is the size in
+                    // bytes of each jump table entry
+                    Inst::AluRRImm12 {
+                        alu_op: AluOPRRI::Slli,
+                        rd: tmp2,
+                        rs: ext_index.to_reg(),
+                        imm12: Imm12::from_i16(2),
+                    }
+                    .emit_uncompressed(sink, emit_info, state, start_off);
 
-                // Emit the jump table.
-                //
-                // Each entry is a auipc + jalr to the target block. We also start with a island
-                // if necessary.
+                    // Load the offset of the target from its jump table entry.
+                    Inst::AluRRR {
+                        alu_op: AluOPRRR::Add,
+                        rd: tmp2,
+                        rs1: tmp1.to_reg(),
+                        rs2: tmp2.to_reg(),
+                    }
+                    .emit_uncompressed(sink, emit_info, state, start_off);
+                    Inst::Load {
+                        rd: tmp2,
+                        op: LoadOP::Lw,
+                        flags: MemFlags::trusted(),
+                        from: AMode::RegOffset(tmp2.to_reg(), table_offset as i64),
+                    }
+                    .emit_uncompressed(sink, emit_info, state, start_off);
 
-                // Emit the jumps back to back
-                for target in targets.iter() {
-                    sink.use_label_at_offset(sink.cur_offset(), *target, LabelUse::PCRel32);
+                    // Offsets are relative to the start of the table, which is
+                    // `table_offset` bytes past the `auipc`.
+                    Inst::AluRRR {
+                        alu_op: AluOPRRR::Add,
+                        rd: tmp1,
+                        rs1: tmp1.to_reg(),
+                        rs2: tmp2.to_reg(),
+                    }
+                    .emit_uncompressed(sink, emit_info, state, start_off);
+                    Inst::Jalr {
+                        rd: writable_zero_reg(),
+                        base: tmp1.to_reg(),
+                        offset: table_offset_imm,
+                    }
+                    .emit_uncompressed(sink, emit_info, state, start_off);
 
-                    Inst::construct_auipc_and_jalr(None, tmp2, 0)
-                        .iter()
-                        .for_each(|i| i.emit_uncompressed(sink, emit_info, state, start_off));
+                    // Emit the jump table.
+                    if sink.cur_offset() % 4 != 0 {
+                        sink.put_inline_data(&[0; 2]);
+                    }
+                    let table_start = sink.cur_offset();
+                    debug_assert_eq!(table_start, auipc_offset + table_offset);
+
+                    // Each entry starts out holding its own offset into the table,
+                    // the label fixup then adds the distance from the entry to the
+                    // target block.
+                    for target in targets.iter() {
+                        let entry_offset = sink.cur_offset();
+                        sink.use_label_at_offset(entry_offset, *target, LabelUse::PCRel32Data);
+                        sink.put_inline_data(&(entry_offset - table_start).to_le_bytes());
+                    }
+                } else {
+                    emit_br_table_tree(
+                        sink,
+                        emit_info,
+                        state,
+                        start_off,
+                        ext_index.to_reg(),
+                        tmp2,
+                        0,
+                        targets,
+                    );
                 }
 
                 // We've just emitted an island that is safe up to *here*.
@@ -1539,6 +1648,24 @@ impl Inst {
 
                 sink.put4(x);
             }
+            &Inst::Amocas {
+                op,
+                rd,
+                e,
+                addr,
+                src,
+                amo,
+            } => {
+                debug_assert_eq!(rd.to_reg(), e);
+                Inst::Atomic {
+                    op,
+                    rd,
+                    addr,
+                    src,
+                    amo,
+                }
+                .emit(sink, emit_info, state);
+            }
             &Inst::Fence { pred, succ } => {
                 let x = 0b0001111
                     | 0b00000 << 7
@@ -1703,6 +1830,7 @@ impl Inst {
                 addr,
                 v,
                 ty,
+                amo,
             } => {
                 //     # addr holds address of memory location
                 //     # e holds expected value
@@ -1714,19 +1842,26 @@ impl Inst {
                 //     sc.w t0, v, (addr)     # Try to update.
                 //     bnez t0 , cas          # if store not ok,retry.
                 // fail:
+                //
+                // Sub-word values are loaded as the containing word into
+                // `t0`, and the desired value is merged into that same word
+                // before the store-conditional, which then overwrites it with
+                // the success flag.
                 let fail_label = sink.get_label();
                 let cas_lebel = sink.get_label();
                 sink.bind_label(cas_lebel, &mut state.ctrl_plane);
+                let subword = ty.bits() < 32;
+                let word = if subword { t0 } else { dst };
                 Inst::Atomic {
                     op: AtomicOP::load_op(ty),
-                    rd: dst,
+                    rd: word,
                     addr,
                     src: zero_reg(),
-                    amo: AMO::SeqCst,
+                    amo: amo.load_reserved(),
                 }
                 .emit(sink, emit_info, state);
-                if ty.bits() < 32 {
-                    AtomicOP::extract(dst, offset, dst.to_reg(), ty)
+                if subword {
+                    AtomicOP::extract(dst, offset, word.to_reg(), ty)
                         .iter()
                         .for_each(|i| i.emit(sink, emit_info, state));
                 } else if ty.bits() == 32 {
@@ -1749,21 +1884,11 @@ impl Inst {
                     },
                 }
                 .emit(sink, emit_info, state);
-                let store_value = if ty.bits() < 32 {
-                    // reload value to t0.
-                    Inst::Atomic {
-                        op: AtomicOP::load_op(ty),
-                        rd: t0,
-                        addr,
-                        src: zero_reg(),
-                        amo: AMO::SeqCst,
-                    }
-                    .emit(sink, emit_info, state);
-                    // set reset part.
-                    AtomicOP::merge(t0, writable_spilltmp_reg(), offset, v, ty)
+                let store_value = if subword {
+                    AtomicOP::merge(word, writable_spilltmp_reg(), offset, v, ty)
                         .iter()
                         .for_each(|i| i.emit(sink, emit_info, state));
-                    t0.to_reg()
+                    word.to_reg()
                 } else {
                     v
                 };
@@ -1772,7 +1897,7 @@ impl Inst {
                     rd: t0,
                     addr,
                     src: store_value,
-                    amo: AMO::SeqCst,
+                    amo: amo.store_conditional(),
                 }
                 .emit(sink, emit_info, state);
                 // check is our value stored.
@@ -1796,29 +1921,43 @@ impl Inst {
                 p,
                 x,
                 t0,
+                word,
+                amo,
             } => {
                 let retry = sink.get_label();
                 sink.bind_label(retry, &mut state.ctrl_plane);
-                // load old value.
+
+                // Sub-word operations work on the aligned word containing the
+                // value. The whole word is kept in `word` so that the
+                // store-conditional writes back the word observed by the
+                // load-reserved with only our lane replaced.
+                let subword = ty.bits() < 32;
+                let word = if subword { word } else { dst };
                 Inst::Atomic {
                     op: AtomicOP::load_op(ty),
-                    rd: dst,
+                    rd: word,
                     addr: p,
                     src: zero_reg(),
-                    amo: AMO::SeqCst,
+                    amo: amo.load_reserved(),
                 }
                 .emit(sink, emit_info, state);
-                //
+                if subword {
+                    let extract = match op {
+                        crate::ir::AtomicRmwOp::Smin | crate::ir::AtomicRmwOp::Smax => {
+                            AtomicOP::extract_sext(dst, offset, word.to_reg(), ty)
+                        }
+                        _ => AtomicOP::extract(dst, offset, word.to_reg(), ty),
+                    };
+                    extract.iter().for_each(|i| i.emit(sink, emit_info, state));
+                }
 
-                let store_value: Reg = match op {
+                // Compute the new value of the lane.
+                let new_value: Reg = match op {
                     crate::ir::AtomicRmwOp::Add
                     | crate::ir::AtomicRmwOp::Sub
                     | crate::ir::AtomicRmwOp::And
                     | crate::ir::AtomicRmwOp::Or
                     | crate::ir::AtomicRmwOp::Xor => {
-                        AtomicOP::extract(dst, offset, dst.to_reg(), ty)
-                            .iter()
-                            .for_each(|i| i.emit(sink, emit_info, state));
                         Inst::AluRRR {
                             alu_op: match op {
                                 crate::ir::AtomicRmwOp::Add => AluOPRRR::Add,
@@ -1833,31 +1972,9 @@ impl Inst {
                             rs2: x,
                         }
                         .emit(sink, emit_info, state);
-                        Inst::Atomic {
-                            op: AtomicOP::load_op(ty),
-                            rd: writable_spilltmp_reg2(),
-                            addr: p,
-                            src: zero_reg(),
-                            amo: AMO::SeqCst,
-                        }
-                        .emit(sink, emit_info, state);
-                        AtomicOP::merge(
-                            writable_spilltmp_reg2(),
-                            writable_spilltmp_reg(),
-                            offset,
-                            t0.to_reg(),
-                            ty,
-                        )
-                        .iter()
-                        .for_each(|i| i.emit(sink, emit_info, state));
-                        spilltmp_reg2()
+                        t0.to_reg()
                     }
                     crate::ir::AtomicRmwOp::Nand => {
-                        if ty.bits() < 32 {
-                            AtomicOP::extract(dst, offset, dst.to_reg(), ty)
-                                .iter()
-                                .for_each(|i| i.emit(sink, emit_info, state));
-                        }
                         Inst::AluRRR {
                             alu_op: AluOPRRR::And,
                             rd: t0,
@@ -1866,28 +1983,7 @@ impl Inst {
                         }
                         .emit(sink, emit_info, state);
                         Inst::construct_bit_not(t0, t0.to_reg()).emit(sink, emit_info, state);
-                        if ty.bits() < 32 {
-                            Inst::Atomic {
-                                op: AtomicOP::load_op(ty),
-                                rd: writable_spilltmp_reg2(),
-                                addr: p,
-                                src: zero_reg(),
-                                amo: AMO::SeqCst,
-                            }
-                            .emit(sink, emit_info, state);
-                            AtomicOP::merge(
-                                writable_spilltmp_reg2(),
-                                writable_spilltmp_reg(),
-                                offset,
-                                t0.to_reg(),
-                                ty,
-                            )
-                            .iter()
-                            .for_each(|i| i.emit(sink, emit_info, state));
-                            spilltmp_reg2()
-                        } else {
-                            t0.to_reg()
-                        }
+                        t0.to_reg()
                     }
 
                     crate::ir::AtomicRmwOp::Umin
@@ -1896,15 +1992,6 @@ impl Inst {
                     | crate::ir::AtomicRmwOp::Smax => {
                         let label_select_dst = sink.get_label();
                         let label_select_done = sink.get_label();
-                        if op == crate::ir::AtomicRmwOp::Umin || op == crate::ir::AtomicRmwOp::Umax
-                        {
-                            AtomicOP::extract(dst, offset, dst.to_reg(), ty)
-                        } else {
-                            AtomicOP::extract_sext(dst, offset, dst.to_reg(), ty)
-                        }
-                        .iter()
-                        .for_each(|i| i.emit(sink, emit_info, state));
-
                         Inst::CondBr {
                             taken: CondBrTarget::Label(label_select_dst),
                             not_taken: CondBrTarget::Fallthrough,
@@ -1927,48 +2014,19 @@ impl Inst {
                         sink.bind_label(label_select_dst, &mut state.ctrl_plane);
                         Inst::gen_move(t0, dst.to_reg(), I64).emit(sink, emit_info, state);
                         sink.bind_label(label_select_done, &mut state.ctrl_plane);
-                        Inst::Atomic {
-                            op: AtomicOP::load_op(ty),
-                            rd: writable_spilltmp_reg2(),
-                            addr: p,
-                            src: zero_reg(),
-                            amo: AMO::SeqCst,
-                        }
-                        .emit(sink, emit_info, state);
-                        AtomicOP::merge(
-                            writable_spilltmp_reg2(),
-                            writable_spilltmp_reg(),
-                            offset,
-                            t0.to_reg(),
-                            ty,
-                        )
-                        .iter()
-                        .for_each(|i| i.emit(sink, emit_info, state));
-                        spilltmp_reg2()
+                        t0.to_reg()
                     }
-                    crate::ir::AtomicRmwOp::Xchg => {
-                        AtomicOP::extract(dst, offset, dst.to_reg(), ty)
-                            .iter()
-                            .for_each(|i| i.emit(sink, emit_info, state));
-                        Inst::Atomic {
-                            op: AtomicOP::load_op(ty),
-                            rd: writable_spilltmp_reg2(),
-                            addr: p,
-                            src: zero_reg(),
-                            amo: AMO::SeqCst,
-                        }
-                        .emit(sink, emit_info, state);
-                        AtomicOP::merge(
-                            writable_spilltmp_reg2(),
-                            writable_spilltmp_reg(),
-                            offset,
-                            x,
-                            ty,
-                        )
+                    crate::ir::AtomicRmwOp::Xchg => x,
+                };
+
+                // Sub-word values are merged back into the loaded word.
+                let store_value = if subword {
+                    AtomicOP::merge(word, writable_spilltmp_reg(), offset, new_value, ty)
                         .iter()
                         .for_each(|i| i.emit(sink, emit_info, state));
-                        spilltmp_reg2()
-                    }
+                    word.to_reg()
+                } else {
+                    new_value
                 };
 
                 Inst::Atomic {
@@ -1976,7 +2034,7 @@ impl Inst {
                     rd: t0,
                     addr: p,
                     src: store_value,
-                    amo: AMO::SeqCst,
+                    amo: amo.store_conditional(),
                 }
                 .emit(sink, emit_info, state);
 
@@ -1997,8 +2055,39 @@ impl Inst {
                 rd,
                 ref name,
                 offset,
+                distance,
             } => {
-                if emit_info.shared_flag.is_pic() {
+                if distance == RelocDistance::Near {
+                    // Colocated symbols end up in the same object as this
+                    // code, so their address is a fixed distance from the PC
+                    // and doesn't need to go through the GOT or an absolute
+                    // address literal.
+                    //
+                    // See: https://github.com/riscv-non-isa/riscv-elf-psabi-doc/blob/master/riscv-elf.adoc#pc-relative-symbol-addresses
+                    //
+                    // Emit the following code:
+                    // label:
+                    //   auipc rd, 0              # R_RISCV_PCREL_HI20 (symbol_name + offset)
+                    //   addi  rd, rd, 0          # R_RISCV_PCREL_LO12_I (label)
+                    let auipc_label = sink.get_label();
+                    sink.bind_label(auipc_label, &mut state.ctrl_plane);
+
+                    sink.add_reloc(Reloc::RiscvPCRelHi20, &**name, offset);
+                    Inst::Auipc {
+                        rd,
+                        imm: Imm20::from_i32(0),
+                    }
+                    .emit_uncompressed(sink, emit_info, state, start_off);
+
+                    sink.add_reloc(Reloc::RiscvPCRelLo12I, &auipc_label, 0);
+                    Inst::AluRRImm12 {
+                        alu_op: AluOPRRI::Addi,
+                        rd,
+                        rs: rd.to_reg(),
+                        imm12: Imm12::ZERO,
+                    }
+                    .emit_uncompressed(sink, emit_info, state, start_off);
+                } else if emit_info.shared_flag.is_pic() {
                     // Load a PC-relative address into a register.
                     // RISC-V does this slightly differently from other arches. We emit a relocation
                     // with a label, instead of the symbol itself.
@@ -2031,6 +2120,32 @@ impl Inst {
                         from: AMode::RegOffset(rd.to_reg(), 0),
                     }
                     .emit_uncompressed(sink, emit_info, state, start_off);
+
+                    // The GOT entry holds the address of the symbol itself, so
+                    // any offset has to be added separately.
+                    if offset != 0 {
+                        if let Some(imm12) = Imm12::maybe_from_i64(offset) {
+                            Inst::AluRRImm12 {
+                                alu_op: AluOPRRI::Addi,
+                                rd,
+                                rs: rd.to_reg(),
+                                imm12,
+                            }
+                            .emit(sink, emit_info, state);
+                        } else {
+                            let tmp = writable_spilltmp_reg();
+                            Inst::load_constant_u64(tmp, offset as u64)
+                                .into_iter()
+                                .for_each(|i| i.emit(sink, emit_info, state));
+                            Inst::AluRRR {
+                                alu_op: AluOPRRR::Add,
+                                rd,
+                                rs1: rd.to_reg(),
+                                rs2: tmp.to_reg(),
+                            }
+                            .emit(sink, emit_info, state);
+                        }
+                    }
                 } else {
                     // In the non PIC sequence we relocate the absolute address into
                     // a prealocatted space, load it into a register and jump over it.
@@ -2059,7 +2174,7 @@ impl Inst {
 
                     sink.bind_label(label_data, &mut state.ctrl_plane);
                     sink.add_reloc(Reloc::Abs8, name.as_ref(), offset);
-                    sink.put8(0);
+                    sink.put_inline_data(&[0; 8]);
 
                     sink.bind_label(label_end, &mut state.ctrl_plane);
                 }
@@ -2112,237 +2227,159 @@ impl Inst {
                 .emit_uncompressed(sink, emit_info, state, start_off);
             }
 
+            &Inst::ElfTlsInitialExec { rd, ref name } => {
+                // The thread pointer offset of the symbol is loaded from its GOT
+                // entry, and then added to the thread pointer.
+                //
+                // label:
+                //    auipc rd,0                    # R_RISCV_TLS_GOT_HI20 (symbol)
+                //    ld    rd,0(rd)                # R_RISCV_PCREL_LO12_I (label)
+                //    add   rd,rd,tp
+                //
+                // https://github.com/riscv-non-isa/riscv-elf-psabi-doc/blob/master/riscv-elf.adoc#initial-exec
+                let auipc_label = sink.get_label();
+                sink.bind_label(auipc_label, &mut state.ctrl_plane);
+
+                sink.add_reloc(Reloc::RiscvTlsGotHi20, &**name, 0);
+                Inst::Auipc {
+                    rd,
+                    imm: Imm20::from_i32(0),
+                }
+                .emit_uncompressed(sink, emit_info, state, start_off);
+
+                sink.add_reloc(Reloc::RiscvPCRelLo12I, &auipc_label, 0);
+                Inst::Load {
+                    rd,
+                    op: LoadOP::Ld,
+                    flags: MemFlags::trusted(),
+                    from: AMode::RegOffset(rd.to_reg(), 0),
+                }
+                .emit_uncompressed(sink, emit_info, state, start_off);
+
+                Inst::AluRRR {
+                    alu_op: AluOPRRR::Add,
+                    rd,
+                    rs1: rd.to_reg(),
+                    rs2: tp_reg(),
+                }
+                .emit_uncompressed(sink, emit_info, state, start_off);
+            }
+
+            &Inst::ElfTlsLocalExec { rd, ref name } => {
+                // The thread pointer offset of the symbol is a link time constant.
+                // The `add` is marked so that the linker can relax the sequence.
+                //
+                //    lui   rd,0                    # R_RISCV_TPREL_HI20 (symbol)
+                //    add   rd,rd,tp                # R_RISCV_TPREL_ADD (symbol)
+                //    addi  rd,rd,0                 # R_RISCV_TPREL_LO12_I (symbol)
+                //
+                // https://github.com/riscv-non-isa/riscv-elf-psabi-doc/blob/master/riscv-elf.adoc#local-exec
+                sink.add_reloc(Reloc::RiscvTPRelHi20, &**name, 0);
+                Inst::Lui {
+                    rd,
+                    imm: Imm20::from_i32(0),
+                }
+                .emit_uncompressed(sink, emit_info, state, start_off);
+
+                sink.add_reloc(Reloc::RiscvTPRelAdd, &**name, 0);
+                Inst::AluRRR {
+                    alu_op: AluOPRRR::Add,
+                    rd,
+                    rs1: rd.to_reg(),
+                    rs2: tp_reg(),
+                }
+                .emit_uncompressed(sink, emit_info, state, start_off);
+
+                sink.add_reloc(Reloc::RiscvTPRelLo12I, &**name, 0);
+                Inst::AluRRImm12 {
+                    alu_op: AluOPRRI::Addi,
+                    rd,
+                    rs: rd.to_reg(),
+                    imm12: Imm12::from_i16(0),
+                }
+                .emit_uncompressed(sink, emit_info, state, start_off);
+            }
+
             &Inst::TrapIf {
                 rs1,
                 rs2,
                 cc,
                 trap_code,
             } => {
-                let label_end = sink.get_label();
-                let cond = IntegerCompare { kind: cc, rs1, rs2 };
-
-                // Jump over the trap if we the condition is false.
+                // Branch to a trap that's emitted out of line, in the next
+                // island, so that the common path falls through.
+                let trap_label = sink.defer_trap(trap_code);
                 Inst::CondBr {
-                    taken: CondBrTarget::Label(label_end),
+                    taken: CondBrTarget::Label(trap_label),
                     not_taken: CondBrTarget::Fallthrough,
-                    kind: cond.inverse(),
+                    kind: IntegerCompare { kind: cc, rs1, rs2 },
                 }
                 .emit(sink, emit_info, state);
-                Inst::Udf { trap_code }.emit(sink, emit_info, state);
-
-                sink.bind_label(label_end, &mut state.ctrl_plane);
             }
             &Inst::Udf { trap_code } => {
                 sink.add_trap(trap_code);
                 sink.put_data(Inst::TRAP_OPCODE);
             }
-            &Inst::AtomicLoad { rd, ty, p } => {
-                // emit the fence.
-                Inst::Fence {
-                    pred: Inst::FENCE_REQ_R | Inst::FENCE_REQ_W,
-                    succ: Inst::FENCE_REQ_R | Inst::FENCE_REQ_W,
+            &Inst::AtomicLoad {
+                rd,
+                ty,
+                p,
+                flags,
+                amo,
+            } => {
+                // Following the RVWMO mapping, only a sequentially consistent
+                // load needs a leading fence, and only loads with acquire
+                // semantics need the trailing one. Under Ztso every load
+                // already has acquire semantics, but the leading fence is
+                // still needed to order it after earlier stores.
+                let ztso = emit_info.isa_flags.has_ztso();
+                if let AMO::SeqCst = amo {
+                    Inst::Fence {
+                        pred: Inst::FENCE_REQ_R | Inst::FENCE_REQ_W,
+                        succ: Inst::FENCE_REQ_R | Inst::FENCE_REQ_W,
+                    }
+                    .emit(sink, emit_info, state);
                 }
-                .emit(sink, emit_info, state);
                 // load.
                 Inst::Load {
                     rd,
                     op: LoadOP::from_type(ty),
-                    flags: MemFlags::new(),
+                    flags,
                     from: AMode::RegOffset(p, 0),
                 }
                 .emit(sink, emit_info, state);
-                Inst::Fence {
-                    pred: Inst::FENCE_REQ_R,
-                    succ: Inst::FENCE_REQ_R | Inst::FENCE_REQ_W,
-                }
-                .emit(sink, emit_info, state);
-            }
-            &Inst::AtomicStore { src, ty, p } => {
-                Inst::Fence {
-                    pred: Inst::FENCE_REQ_R | Inst::FENCE_REQ_W,
-                    succ: Inst::FENCE_REQ_W,
-                }
-                .emit(sink, emit_info, state);
-                Inst::Store {
-                    to: AMode::RegOffset(p, 0),
-                    op: StoreOP::from_type(ty),
-                    flags: MemFlags::new(),
-                    src,
-                }
-                .emit(sink, emit_info, state);
-            }
-
-            &Inst::Popcnt {
-                sum,
-                tmp,
-                step,
-                rs,
-                ty,
-            } => {
-                // load 0 to sum , init.
-                Inst::gen_move(sum, zero_reg(), I64).emit(sink, emit_info, state);
-                // load
-                Inst::load_imm12(step, Imm12::from_i16(ty.bits() as i16))
-                    .emit(sink, emit_info, state);
-                //
-                Inst::load_imm12(tmp, Imm12::ONE).emit(sink, emit_info, state);
-                Inst::AluRRImm12 {
-                    alu_op: AluOPRRI::Slli,
-                    rd: tmp,
-                    rs: tmp.to_reg(),
-                    imm12: Imm12::from_i16((ty.bits() - 1) as i16),
-                }
-                .emit(sink, emit_info, state);
-                let label_done = sink.get_label();
-                let label_loop = sink.get_label();
-                sink.bind_label(label_loop, &mut state.ctrl_plane);
-                Inst::CondBr {
-                    taken: CondBrTarget::Label(label_done),
-                    not_taken: CondBrTarget::Fallthrough,
-                    kind: IntegerCompare {
-                        kind: IntCC::SignedLessThanOrEqual,
-                        rs1: step.to_reg(),
-                        rs2: zero_reg(),
-                    },
-                }
-                .emit(sink, emit_info, state);
-                // test and add sum.
-                {
-                    Inst::AluRRR {
-                        alu_op: AluOPRRR::And,
-                        rd: writable_spilltmp_reg2(),
-                        rs1: tmp.to_reg(),
-                        rs2: rs,
-                    }
-                    .emit(sink, emit_info, state);
-                    let label_over = sink.get_label();
-                    Inst::CondBr {
-                        taken: CondBrTarget::Label(label_over),
-                        not_taken: CondBrTarget::Fallthrough,
-                        kind: IntegerCompare {
-                            kind: IntCC::Equal,
-                            rs1: zero_reg(),
-                            rs2: spilltmp_reg2(),
-                        },
-                    }
-                    .emit(sink, emit_info, state);
-                    Inst::AluRRImm12 {
-                        alu_op: AluOPRRI::Addi,
-                        rd: sum,
-                        rs: sum.to_reg(),
-                        imm12: Imm12::ONE,
-                    }
-                    .emit(sink, emit_info, state);
-                    sink.bind_label(label_over, &mut state.ctrl_plane);
-                }
-                // set step and tmp.
-                {
-                    Inst::AluRRImm12 {
-                        alu_op: AluOPRRI::Addi,
-                        rd: step,
-                        rs: step.to_reg(),
-                        imm12: Imm12::from_i16(-1),
+                if amo.is_acquire() && !ztso {
+                    Inst::Fence {
+                        pred: Inst::FENCE_REQ_R,
+                        succ: Inst::FENCE_REQ_R | Inst::FENCE_REQ_W,
                     }
                     .emit(sink, emit_info, state);
-                    Inst::AluRRImm12 {
-                        alu_op: AluOPRRI::Srli,
-                        rd: tmp,
-                        rs: tmp.to_reg(),
-                        imm12: Imm12::ONE,
-                    }
-                    .emit(sink, emit_info, state);
-                    Inst::gen_jump(label_loop).emit(sink, emit_info, state);
                 }
-                sink.bind_label(label_done, &mut state.ctrl_plane);
             }
-            &Inst::Cltz {
-                sum,
-                tmp,
-                step,
-                rs,
-                leading,
+            &Inst::AtomicStore {
+                src,
                 ty,
+                p,
+                flags,
+                amo,
             } => {
-                // load 0 to sum , init.
-                Inst::gen_move(sum, zero_reg(), I64).emit(sink, emit_info, state);
-                // load
-                Inst::load_imm12(step, Imm12::from_i16(ty.bits() as i16))
-                    .emit(sink, emit_info, state);
-                //
-                Inst::load_imm12(tmp, Imm12::ONE).emit(sink, emit_info, state);
-                if leading {
-                    Inst::AluRRImm12 {
-                        alu_op: AluOPRRI::Slli,
-                        rd: tmp,
-                        rs: tmp.to_reg(),
-                        imm12: Imm12::from_i16((ty.bits() - 1) as i16),
+                // Stores always have release semantics under Ztso.
+                if amo.is_release() && !emit_info.isa_flags.has_ztso() {
+                    Inst::Fence {
+                        pred: Inst::FENCE_REQ_R | Inst::FENCE_REQ_W,
+                        succ: Inst::FENCE_REQ_W,
                     }
                     .emit(sink, emit_info, state);
                 }
-                let label_done = sink.get_label();
-                let label_loop = sink.get_label();
-                sink.bind_label(label_loop, &mut state.ctrl_plane);
-                Inst::CondBr {
-                    taken: CondBrTarget::Label(label_done),
-                    not_taken: CondBrTarget::Fallthrough,
-                    kind: IntegerCompare {
-                        kind: IntCC::SignedLessThanOrEqual,
-                        rs1: step.to_reg(),
-                        rs2: zero_reg(),
-                    },
+                Inst::Store {
+                    to: AMode::RegOffset(p, 0),
+                    op: StoreOP::from_type(ty),
+                    flags,
+                    src,
                 }
                 .emit(sink, emit_info, state);
-                // test and add sum.
-                {
-                    Inst::AluRRR {
-                        alu_op: AluOPRRR::And,
-                        rd: writable_spilltmp_reg2(),
-                        rs1: tmp.to_reg(),
-                        rs2: rs,
-                    }
-                    .emit(sink, emit_info, state);
-                    Inst::CondBr {
-                        taken: CondBrTarget::Label(label_done),
-                        not_taken: CondBrTarget::Fallthrough,
-                        kind: IntegerCompare {
-                            kind: IntCC::NotEqual,
-                            rs1: zero_reg(),
-                            rs2: spilltmp_reg2(),
-                        },
-                    }
-                    .emit(sink, emit_info, state);
-                    Inst::AluRRImm12 {
-                        alu_op: AluOPRRI::Addi,
-                        rd: sum,
-                        rs: sum.to_reg(),
-                        imm12: Imm12::ONE,
-                    }
-                    .emit(sink, emit_info, state);
-                }
-                // set step and tmp.
-                {
-                    Inst::AluRRImm12 {
-                        alu_op: AluOPRRI::Addi,
-                        rd: step,
-                        rs: step.to_reg(),
-                        imm12: Imm12::from_i16(-1),
-                    }
-                    .emit(sink, emit_info, state);
-                    Inst::AluRRImm12 {
-                        alu_op: if leading {
-                            AluOPRRI::Srli
-                        } else {
-                            AluOPRRI::Slli
-                        },
-                        rd: tmp,
-                        rs: tmp.to_reg(),
-                        imm12: Imm12::ONE,
-                    }
-                    .emit(sink, emit_info, state);
-                    Inst::gen_jump(label_loop).emit(sink, emit_info, state);
-                }
-                sink.bind_label(label_done, &mut state.ctrl_plane);
             }
+
             &Inst::Brev8 {
                 rs,
                 ty,
@@ -2389,7 +2426,7 @@ impl Inst {
                 {
                     Inst::AluRRR {
                         alu_op: AluOPRRR::And,
-                        rd: writable_spilltmp_reg2(),
+                        rd: writable_spilltmp_reg(),
                         rs1: tmp.to_reg(),
                         rs2: rs,
                     }
@@ -2401,7 +2438,7 @@ impl Inst {
                         kind: IntegerCompare {
                             kind: IntCC::Equal,
                             rs1: zero_reg(),
-                            rs2: spilltmp_reg2(),
+                            rs2: spilltmp_reg(),
                         },
                     }
                     .emit(sink, emit_info, state);
@@ -2436,13 +2473,13 @@ impl Inst {
                         // if (step %=8 != 0) then tmp2 = tmp2 << 1
                         let label_over = sink.get_label();
                         let label_sll_1 = sink.get_label();
-                        Inst::load_imm12(writable_spilltmp_reg2(), Imm12::from_i16(8))
+                        Inst::load_imm12(writable_spilltmp_reg(), Imm12::from_i16(8))
                             .emit(sink, emit_info, state);
                         Inst::AluRRR {
                             alu_op: AluOPRRR::Rem,
-                            rd: writable_spilltmp_reg2(),
+                            rd: writable_spilltmp_reg(),
                             rs1: step.to_reg(),
-                            rs2: spilltmp_reg2(),
+                            rs2: spilltmp_reg(),
                         }
                         .emit(sink, emit_info, state);
                         Inst::CondBr {
@@ -2450,7 +2487,7 @@ impl Inst {
                             not_taken: CondBrTarget::Fallthrough,
                             kind: IntegerCompare {
                                 kind: IntCC::NotEqual,
-                                rs1: spilltmp_reg2(),
+                                rs1: spilltmp_reg(),
                                 rs2: zero_reg(),
                             },
                         }
@@ -2503,21 +2540,24 @@ impl Inst {
                     },
                 }
                 .emit(sink, emit_info, state);
-                // compute address.
-                Inst::AluRRR {
+                // Turn `step` into the probe address and back again, as
+                // `sp - (sp - step) == step`, so that no other register is
+                // needed for the address.
+                let flip_step = Inst::AluRRR {
                     alu_op: AluOPRRR::Sub,
-                    rd: writable_spilltmp_reg2(),
+                    rd: step,
                     rs1: stack_reg(),
                     rs2: step.to_reg(),
-                }
-                .emit(sink, emit_info, state);
+                };
+                flip_step.emit(sink, emit_info, state);
                 Inst::Store {
-                    to: AMode::RegOffset(spilltmp_reg2(), 0),
+                    to: AMode::RegOffset(step.to_reg(), 0),
                     op: StoreOP::Sb,
                     flags: MemFlags::new(),
                     src: zero_reg(),
                 }
                 .emit(sink, emit_info, state);
+                flip_step.emit(sink, emit_info, state);
                 // reset step.
                 Inst::AluRRR {
                     alu_op: AluOPRRR::Sub,
@@ -2711,6 +2751,95 @@ impl Inst {
     }
 }
 
+/// Tables with at most this many entries are dispatched with a branch tree even
+/// when jump tables are enabled.
+const MAX_BR_TABLE_TREE_TARGETS: usize = 2;
+
+/// Emits a balanced tree of comparisons dispatching `index` to `targets`, where
+/// `targets[i]` is the target for `index == first + i`. The index has already
+/// been checked against the bounds of the table.
+///
+/// Every node jumps to its upper half with an `auipc`+`jalr`, and leaves do the
+/// same to reach their target, so the tree isn't limited by branch ranges.
+fn emit_br_table_tree(
+    sink: &mut MachBuffer<Inst>,
+    emit_info: &EmitInfo,
+    state: &mut EmitState,
+    start_off: &mut u32,
+    index: Reg,
+    tmp: Writable<Reg>,
+    first: u32,
+    targets: &[MachLabel],
+) {
+    match targets {
+        // Only the default target, which the bounds check has already taken.
+        [] => return,
+        [target] => {
+            sink.use_label_at_offset(sink.cur_offset(), *target, LabelUse::PCRel32);
+            Inst::construct_auipc_and_jalr(None, tmp, 0)
+                .iter()
+                .for_each(|i| i.emit_uncompressed(sink, emit_info, state, start_off));
+            return;
+        }
+        _ => {}
+    }
+
+    // Build the following sequence:
+    //
+    //     li      tmp, first + mid
+    //     bltu    index, tmp, lower_half
+    //     auipc   tmp, 0
+    //     jalr    zero, tmp, upper_half
+    // lower_half:
+    //     ...
+    // upper_half:
+    //     ...
+    let mid = targets.len() / 2;
+    let label_lower = sink.get_label();
+    let label_upper = sink.get_label();
+
+    Inst::load_constant_u32(tmp, u64::from(first) + mid as u64)
+        .iter()
+        .for_each(|i| i.emit(sink, emit_info, state));
+    Inst::CondBr {
+        taken: CondBrTarget::Label(label_lower),
+        not_taken: CondBrTarget::Fallthrough,
+        kind: IntegerCompare {
+            kind: IntCC::UnsignedLessThan,
+            rs1: index,
+            rs2: tmp.to_reg(),
+        },
+    }
+    .emit(sink, emit_info, state);
+    sink.use_label_at_offset(sink.cur_offset(), label_upper, LabelUse::PCRel32);
+    Inst::construct_auipc_and_jalr(None, tmp, 0)
+        .iter()
+        .for_each(|i| i.emit_uncompressed(sink, emit_info, state, start_off));
+
+    sink.bind_label(label_lower, &mut state.ctrl_plane);
+    emit_br_table_tree(
+        sink,
+        emit_info,
+        state,
+        start_off,
+        index,
+        tmp,
+        first,
+        &targets[..mid],
+    );
+    sink.bind_label(label_upper, &mut state.ctrl_plane);
+    emit_br_table_tree(
+        sink,
+        emit_info,
+        state,
+        start_off,
+        index,
+        tmp,
+        first + mid as u32,
+        &targets[mid..],
+    );
+}
+
 fn emit_return_call_common_sequence<T>(
     sink: &mut MachBuffer<Inst>,
     emit_info: &EmitInfo,
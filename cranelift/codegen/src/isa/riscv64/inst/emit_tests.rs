@@ -2078,6 +2078,54 @@ fn test_riscv64_binemit() {
         "amomaxu.d a0,a2,(a1)",
         0xe0c5b52f,
     ));
+    insns.push(TestUnit::new(
+        Inst::Amocas {
+            op: AtomicOP::AmocasW,
+            rd: writable_a0(),
+            e: a0(),
+            addr: a1(),
+            src: a2(),
+            amo: AMO::SeqCst,
+        },
+        "amocas.w.aqrl a0,a2,(a1)",
+        0x2ec5a52f,
+    ));
+    insns.push(TestUnit::new(
+        Inst::Amocas {
+            op: AtomicOP::AmocasW,
+            rd: writable_a0(),
+            e: a0(),
+            addr: a1(),
+            src: a2(),
+            amo: AMO::Acquire,
+        },
+        "amocas.w.aq a0,a2,(a1)",
+        0x2cc5a52f,
+    ));
+    insns.push(TestUnit::new(
+        Inst::Amocas {
+            op: AtomicOP::AmocasD,
+            rd: writable_a0(),
+            e: a0(),
+            addr: a1(),
+            src: a2(),
+            amo: AMO::Relax,
+        },
+        "amocas.d a0,a2,(a1)",
+        0x28c5b52f,
+    ));
+    insns.push(TestUnit::new(
+        Inst::Amocas {
+            op: AtomicOP::AmocasD,
+            rd: writable_a0(),
+            e: a0(),
+            addr: a1(),
+            src: a2(),
+            amo: AMO::Release,
+        },
+        "amocas.d.rl a0,a2,(a1)",
+        0x2ac5b52f,
+    ));
 
     /////////
     insns.push(TestUnit::new(
@@ -2088,6 +2136,19 @@ fn test_riscv64_binemit() {
         "fence w,r",
         0x120000f,
     ));
+    insns.push(TestUnit::new(
+        Inst::Fence {
+            pred: Inst::FENCE_REQ_R | Inst::FENCE_REQ_W,
+            succ: Inst::FENCE_REQ_R | Inst::FENCE_REQ_W,
+        },
+        "fence rw,rw",
+        0x330000f,
+    ));
+    insns.push(TestUnit::new(
+        Inst::Fence { pred: 15, succ: 15 },
+        "fence iorw,iorw",
+        0xff0000f,
+    ));
     insns.push(TestUnit::new(Inst::EBreak {}, "ebreak", 0x100073));
 
     insns.push(TestUnit::new(
@@ -2170,6 +2231,71 @@ fn test_riscv64_binemit() {
         0xf2168553,
     ));
 
+    insns.push(TestUnit::new(
+        Inst::Fli {
+            width: FpuOPWidth::S,
+            rd: writable_fa0(),
+            imm: FliConstant::new(16),
+        },
+        "fli.s fa0,1.0",
+        0xf0180553,
+    ));
+
+    insns.push(TestUnit::new(
+        Inst::Fli {
+            width: FpuOPWidth::D,
+            rd: writable_fa0(),
+            imm: FliConstant::new(16),
+        },
+        "fli.d fa0,1.0",
+        0xf2180553,
+    ));
+
+    insns.push(TestUnit::new(
+        Inst::Fli {
+            width: FpuOPWidth::S,
+            rd: writable_fa0(),
+            imm: FliConstant::new(31),
+        },
+        "fli.s fa0,nan",
+        0xf01f8553,
+    ));
+
+    insns.push(TestUnit::new(
+        Inst::Fli {
+            width: FpuOPWidth::D,
+            rd: writable_fa0(),
+            imm: FliConstant::new(30),
+        },
+        "fli.d fa0,inf",
+        0xf21f0553,
+    ));
+
+    // Floating-point zero is moved from `zero`.
+    insns.push(TestUnit::new(
+        Inst::FpuRR {
+            frm: FRM::RNE,
+            width: FpuOPWidth::S,
+            alu_op: FpuOPRR::FmvFmtX,
+            rd: writable_fa0(),
+            rs: zero_reg(),
+        },
+        "fmv.w.x fa0,zero",
+        0xf0000553,
+    ));
+
+    insns.push(TestUnit::new(
+        Inst::FpuRR {
+            frm: FRM::RNE,
+            width: FpuOPWidth::D,
+            alu_op: FpuOPRR::FmvFmtX,
+            rd: writable_fa0(),
+            rs: zero_reg(),
+        },
+        "fmv.d.x fa0,zero",
+        0xf2000553,
+    ));
+
     let (flags, isa_flags) = make_test_flags();
     let emit_info = EmitInfo::new(flags, isa_flags);
 
@@ -2196,31 +2322,13 @@ fn make_test_flags() -> (settings::Flags, super::super::riscv_settings::Flags) {
     (flags, isa_flags)
 }
 
-#[test]
-fn riscv64_worst_case_instruction_size() {
-    let (flags, isa_flags) = make_test_flags();
-    let emit_info = EmitInfo::new(flags, isa_flags);
-
-    // These are all candidate instructions with potential to generate a lot of bytes.
+/// All candidate instructions with potential to generate a lot of bytes.
+///
+/// Return Call Indirect and BrTable are the largest instructions possible. However they
+/// emit their own island, so we don't account them here.
+fn worst_case_size_candidates() -> Vec<MInst> {
     let mut candidates: Vec<MInst> = vec![];
 
-    candidates.push(Inst::Popcnt {
-        sum: writable_a0(),
-        tmp: writable_a0(),
-        step: writable_a0(),
-        rs: a0(),
-        ty: I64,
-    });
-
-    candidates.push(Inst::Cltz {
-        sum: writable_a0(),
-        tmp: writable_a0(),
-        step: writable_a0(),
-        rs: a0(),
-        leading: true,
-        ty: I64,
-    });
-
     candidates.push(Inst::Brev8 {
         rd: writable_a0(),
         tmp: writable_a0(),
@@ -2238,6 +2346,7 @@ fn riscv64_worst_case_instruction_size() {
         addr: a0(),
         v: a0(),
         ty: I64,
+        amo: AMO::SeqCst,
     });
 
     candidates.push(Inst::AtomicCas {
@@ -2248,6 +2357,7 @@ fn riscv64_worst_case_instruction_size() {
         addr: a0(),
         v: a0(),
         ty: I16,
+        amo: AMO::SeqCst,
     });
 
     candidates.extend(
@@ -2261,25 +2371,1744 @@ fn riscv64_worst_case_instruction_size() {
                 p: a1(),
                 x: a2(),
                 t0: writable_a0(),
+                word: Writable::from_reg(x_reg(13)),
+                amo: AMO::SeqCst,
             }),
     );
 
-    // Return Call Indirect and BrTable are the largest instructions possible. However they
-    // emit their own island, so we don't account them here.
+    candidates.extend([I64, I32, I16, I8].into_iter().flat_map(|ty| {
+        [
+            Inst::AtomicLoad {
+                rd: writable_a0(),
+                ty,
+                p: a1(),
+                flags: MemFlags::new(),
+                amo: AMO::SeqCst,
+            },
+            Inst::AtomicStore {
+                src: a0(),
+                ty,
+                p: a1(),
+                flags: MemFlags::new(),
+                amo: AMO::SeqCst,
+            },
+        ]
+    }));
+
+    // Symbol addresses, with an offset that takes a full 64-bit constant to
+    // materialize when it can't be folded into the relocation.
+    candidates.extend([RelocDistance::Near, RelocDistance::Far].map(|distance| {
+        Inst::LoadExtName {
+            rd: writable_a0(),
+            name: Box::new(ExternalName::testcase("sym")),
+            offset: 0x1234_5678_9abc_def1,
+            distance,
+        }
+    }));
+    let name = || Box::new(ExternalName::testcase("sym"));
+    candidates.push(Inst::ElfTlsGetAddr {
+        rd: writable_a0(),
+        name: name(),
+    });
+    candidates.push(Inst::ElfTlsInitialExec {
+        rd: writable_a0(),
+        name: name(),
+    });
+    candidates.push(Inst::ElfTlsLocalExec {
+        rd: writable_a0(),
+        name: name(),
+    });
+
+    candidates.push(Inst::LoadInlineConst {
+        rd: writable_a0(),
+        ty: I64,
+        imm: 0x1234_5678_9abc_def1,
+    });
+
+    // 128-bit selects move a pair of registers on each side.
+    candidates.push(Inst::Select {
+        dst: ValueRegs::two(writable_a0(), writable_a1()),
+        condition: IntegerCompare {
+            kind: IntCC::SignedLessThan,
+            rs1: a2(),
+            rs2: zero_reg(),
+        },
+        x: ValueRegs::two(x_reg(13), x_reg(14)),
+        y: ValueRegs::two(x_reg(15), x_reg(16)),
+    });
+
+    // The largest probe loop: both constants take the longest sequence to
+    // materialize.
+    candidates.push(Inst::StackProbeLoop {
+        guard_size: 0xffff_fff1,
+        probe_count: 0xffff_fff1,
+        tmp: writable_a0(),
+    });
+
+    candidates
+}
+
+#[test]
+fn riscv64_worst_case_instruction_size() {
+    use crate::settings::Configurable;
+
+    let (flags, isa_flags) = make_test_flags();
+    let emit_info = EmitInfo::new(flags.clone(), isa_flags);
+    let candidates = worst_case_size_candidates();
+
+    let mut pic_flags = settings::builder();
+    pic_flags.enable("is_pic").unwrap();
+    let pic_emit_info = EmitInfo::new(
+        settings::Flags::new(pic_flags),
+        super::super::riscv_settings::Flags::new(&flags, &super::super::riscv_settings::builder()),
+    );
 
     let mut max: (u32, MInst) = (0, Inst::Nop0);
     for i in candidates {
-        let mut buffer = MachBuffer::new();
-        let mut emit_state = Default::default();
-        i.emit(&mut buffer, &emit_info, &mut emit_state);
-        let buffer = buffer.finish(&Default::default(), &mut Default::default());
-        let length = buffer.data().len() as u32;
-        if length > max.0 {
+        for emit_info in [&emit_info, &pic_emit_info] {
+            let mut buffer = MachBuffer::new();
+            let mut emit_state = Default::default();
+            i.emit(&mut buffer, emit_info, &mut emit_state);
+            let buffer = buffer.finish(&Default::default(), &mut Default::default());
             let length = buffer.data().len() as u32;
-            max = (length, i.clone());
+            if length > max.0 {
+                max = (length, i.clone());
+            }
+            println!("insn:{i:?}  length: {length}");
         }
-        println!("insn:{i:?}  length: {length}");
     }
     println!("calculate max size is {} , inst is {:?}", max.0, max.1);
     assert!(max.0 <= Inst::worst_case_size());
 }
+
+#[test]
+fn riscv64_ebreak() {
+    use crate::settings::Configurable;
+
+    let (flags, isa_flags) = make_test_flags();
+    let mut b = super::super::riscv_settings::builder();
+    b.enable("has_zca").unwrap();
+    let compressed_isa_flags = super::super::riscv_settings::Flags::new(&flags, &b);
+
+    // `ebreak` is 0x00100073, and `c.ebreak` is 0x9002.
+    for (isa_flags, expected) in [
+        (isa_flags, &[0x73, 0x00, 0x10, 0x00][..]),
+        (compressed_isa_flags, &[0x02, 0x90][..]),
+    ] {
+        let emit_info = EmitInfo::new(flags.clone(), isa_flags);
+        let mut buffer = MachBuffer::new();
+        Inst::EBreak.emit(&mut buffer, &emit_info, &mut Default::default());
+        let buffer = buffer.finish(&Default::default(), &mut Default::default());
+        assert_eq!(buffer.data(), expected);
+    }
+}
+
+#[test]
+fn riscv64_trap_if_out_of_line() {
+    use crate::ir::{RelSourceLoc, TrapCode};
+
+    let (flags, isa_flags) = make_test_flags();
+    let emit_info = EmitInfo::new(flags, isa_flags);
+
+    // The trap is deferred to the end of the function, and keeps the code
+    // and source location of the `TrapIf`.
+    let loc = RelSourceLoc::new(42);
+    let mut buffer = MachBuffer::new();
+    buffer.start_srcloc(loc);
+    Inst::TrapIf {
+        rs1: a0(),
+        rs2: a1(),
+        cc: IntCC::UnsignedLessThan,
+        trap_code: TrapCode::INTEGER_OVERFLOW,
+    }
+    .emit(&mut buffer, &emit_info, &mut Default::default());
+    buffer.end_srcloc();
+    Inst::Nop4.emit(&mut buffer, &emit_info, &mut Default::default());
+    let buffer = buffer.finish(&Default::default(), &mut Default::default());
+
+    // bltu a0, a1, 8; nop; <trap>
+    let words: Vec<u32> = buffer
+        .data()
+        .chunks(4)
+        .map(|w| u32::from_le_bytes(w.try_into().unwrap()))
+        .collect();
+    assert_eq!(words, [0x00b56463, 0x00000013, 0x00000000]);
+
+    let traps: Vec<_> = buffer
+        .traps()
+        .iter()
+        .map(|trap| (trap.offset, trap.code))
+        .collect();
+    assert_eq!(traps, [(8, TrapCode::INTEGER_OVERFLOW)]);
+    assert!(
+        buffer
+            .get_srclocs_sorted()
+            .iter()
+            .any(|srcloc| srcloc.start <= 8 && 8 < srcloc.end && srcloc.loc == loc)
+    );
+}
+
+#[test]
+fn riscv64_load_store_trap_records() {
+    use crate::ir::TrapCode;
+    use crate::settings::Configurable;
+
+    let (flags, isa_flags) = make_test_flags();
+    let emit_info = EmitInfo::new(flags.clone(), isa_flags);
+
+    let mut b = super::super::riscv_settings::builder();
+    b.enable("has_zca").unwrap();
+    let compressed_emit_info = EmitInfo::new(
+        flags.clone(),
+        super::super::riscv_settings::Flags::new(&flags, &b),
+    );
+
+    let heap_oob = MemFlags::new();
+    let user_trap = TrapCode::unwrap_user(1);
+    let user_flags = MemFlags::new().with_trap_code(Some(user_trap));
+
+    // Each instruction along with the expected code size and trap records.
+    let cases: Vec<(&EmitInfo, Inst, u32, Vec<(u32, TrapCode)>)> = vec![
+        // A load that can trap registers the offset of the load itself.
+        (
+            &emit_info,
+            Inst::Load {
+                rd: writable_a0(),
+                op: LoadOP::Ld,
+                flags: heap_oob,
+                from: AMode::RegOffset(a1(), 8),
+            },
+            4,
+            vec![(0, TrapCode::HEAP_OUT_OF_BOUNDS)],
+        ),
+        // Extending loads record the trap code from their flags.
+        (
+            &emit_info,
+            Inst::Load {
+                rd: writable_a0(),
+                op: LoadOP::Lhu,
+                flags: user_flags,
+                from: AMode::RegOffset(a1(), -2),
+            },
+            4,
+            vec![(0, user_trap)],
+        ),
+        // Trusted accesses can't trap and record nothing.
+        (
+            &emit_info,
+            Inst::Load {
+                rd: writable_a0(),
+                op: LoadOP::Ld,
+                flags: MemFlags::trusted(),
+                from: AMode::RegOffset(a1(), 8),
+            },
+            4,
+            vec![],
+        ),
+        (
+            &emit_info,
+            Inst::Store {
+                src: a0(),
+                op: StoreOP::Sb,
+                flags: heap_oob,
+                to: AMode::RegOffset(a1(), 3),
+            },
+            4,
+            vec![(0, TrapCode::HEAP_OUT_OF_BOUNDS)],
+        ),
+        (
+            &emit_info,
+            Inst::Store {
+                src: a0(),
+                op: StoreOP::Sd,
+                flags: MemFlags::trusted(),
+                to: AMode::RegOffset(a1(), 8),
+            },
+            4,
+            vec![],
+        ),
+        // Offsets that don't fit in an imm12 compute the address first, the
+        // trap is recorded on the final memory access.
+        (
+            &emit_info,
+            Inst::Load {
+                rd: writable_a0(),
+                op: LoadOP::Lw,
+                flags: heap_oob,
+                from: AMode::RegOffset(a1(), 0x12345),
+            },
+            16,
+            vec![(12, TrapCode::HEAP_OUT_OF_BOUNDS)],
+        ),
+        (
+            &emit_info,
+            Inst::Store {
+                src: a0(),
+                op: StoreOP::Sw,
+                flags: heap_oob,
+                to: AMode::RegOffset(a1(), 0x12345),
+            },
+            16,
+            vec![(12, TrapCode::HEAP_OUT_OF_BOUNDS)],
+        ),
+        // Without Zfhmin `fsh` is a move to an integer register followed by a
+        // `sh`, which is the instruction that can trap.
+        (
+            &emit_info,
+            Inst::Store {
+                src: fa7(),
+                op: StoreOP::Fsh,
+                flags: heap_oob,
+                to: AMode::RegOffset(a1(), 2),
+            },
+            8,
+            vec![(4, TrapCode::HEAP_OUT_OF_BOUNDS)],
+        ),
+        // Compressed forms record the trap too.
+        (
+            &compressed_emit_info,
+            Inst::Load {
+                rd: writable_a0(),
+                op: LoadOP::Ld,
+                flags: heap_oob,
+                from: AMode::RegOffset(a1(), 8),
+            },
+            2,
+            vec![(0, TrapCode::HEAP_OUT_OF_BOUNDS)],
+        ),
+        (
+            &compressed_emit_info,
+            Inst::Store {
+                src: a0(),
+                op: StoreOP::Sd,
+                flags: heap_oob,
+                to: AMode::SPOffset(16),
+            },
+            2,
+            vec![(0, TrapCode::HEAP_OUT_OF_BOUNDS)],
+        ),
+    ];
+
+    for (emit_info, inst, size, traps) in cases {
+        let mut buffer = MachBuffer::new();
+        inst.emit(&mut buffer, emit_info, &mut Default::default());
+        let buffer = buffer.finish(&Default::default(), &mut Default::default());
+        assert_eq!(buffer.data().len() as u32, size, "{inst:?}");
+        let actual: Vec<_> = buffer
+            .traps()
+            .iter()
+            .map(|trap| (trap.offset, trap.code))
+            .collect();
+        assert_eq!(actual, traps, "{inst:?}");
+    }
+}
+
+#[test]
+fn riscv64_atomic_ordering_bits() {
+    let (flags, isa_flags) = make_test_flags();
+    let emit_info = EmitInfo::new(flags, isa_flags);
+
+    let words = |inst: Inst| -> Vec<u32> {
+        let mut buffer = MachBuffer::new();
+        inst.emit(&mut buffer, &emit_info, &mut Default::default());
+        let buffer = buffer.finish(&Default::default(), &mut Default::default());
+        buffer
+            .data()
+            .chunks(4)
+            .map(|c| u32::from_le_bytes(c.try_into().unwrap()))
+            .collect()
+    };
+    // The `aq`/`rl` bits of an AMO, LR or SC instruction.
+    let ordering = |word: u32| (word >> 25) & 0b11;
+    let is_amo = |word: u32| word & 0x7f == 0b0101111;
+    let is_lr = |word: u32| is_amo(word) && word >> 27 == 0b00010;
+    let is_sc = |word: u32| is_amo(word) && word >> 27 == 0b00011;
+    let fences = |words: &[u32]| words.iter().filter(|w| *w & 0x7f == 0b0001111).count();
+
+    for amo in [AMO::Relax, AMO::Acquire, AMO::Release, AMO::SeqCst] {
+        // A single AMO carries the requested ordering as is.
+        let amoadd = words(Inst::Atomic {
+            op: AtomicOP::AmoaddD,
+            rd: writable_a0(),
+            addr: a1(),
+            src: a2(),
+            amo,
+        });
+        assert_eq!(ordering(amoadd[0]), amo.as_u32(), "{amo:?}");
+
+        // LR/SC loops split the ordering between the `lr` and the `sc`.
+        let loops = [
+            words(Inst::AtomicRmwLoop {
+                offset: zero_reg(),
+                op: crate::ir::AtomicRmwOp::Nand,
+                dst: writable_a0(),
+                ty: I64,
+                p: a1(),
+                x: a2(),
+                t0: Writable::from_reg(x_reg(13)),
+                word: writable_zero_reg(),
+                amo,
+            }),
+            words(Inst::AtomicCas {
+                offset: zero_reg(),
+                t0: Writable::from_reg(x_reg(13)),
+                dst: writable_a0(),
+                e: a2(),
+                addr: a1(),
+                v: x_reg(14),
+                ty: I8,
+                amo,
+            }),
+        ];
+        for code in loops {
+            let lr = code.iter().copied().find(|w| is_lr(*w)).unwrap();
+            let sc = code.iter().copied().find(|w| is_sc(*w)).unwrap();
+            assert_eq!(ordering(lr), amo.load_reserved().as_u32(), "{amo:?}");
+            assert_eq!(ordering(sc), amo.store_conditional().as_u32(), "{amo:?}");
+            assert_eq!(fences(&code), 0);
+        }
+
+        // Atomic loads and stores are plain accesses surrounded by fences.
+        let load = words(Inst::AtomicLoad {
+            rd: writable_a0(),
+            ty: I32,
+            p: a1(),
+            flags: MemFlags::new(),
+            amo,
+        });
+        let expected = match amo {
+            AMO::Relax | AMO::Release => 0,
+            AMO::Acquire => 1,
+            AMO::SeqCst => 2,
+        };
+        assert_eq!(fences(&load), expected, "{amo:?}");
+
+        let store = words(Inst::AtomicStore {
+            src: a0(),
+            ty: I32,
+            p: a1(),
+            flags: MemFlags::new(),
+            amo,
+        });
+        let expected = match amo {
+            AMO::Relax | AMO::Acquire => 0,
+            AMO::Release | AMO::SeqCst => 1,
+        };
+        assert_eq!(fences(&store), expected, "{amo:?}");
+    }
+}
+
+/// Address of the memory that `run_atomic_sequence` emulates.
+const ATOMIC_MEM_BASE: u64 = 0x1000;
+
+/// Runs the straight-line `code` of an atomic sequence on the registers `x`,
+/// with `mem` mapped at `ATOMIC_MEM_BASE`, until it falls off its end.
+///
+/// Only the instructions that LR/SC loops are made of are supported. Every
+/// `sc` succeeds, as there is no other hart to break the reservation.
+fn run_atomic_sequence(code: &[u8], x: &mut [u64; 32], mem: &mut [u8; 16]) {
+    let word = |mem: &[u8; 16], addr: u64, bytes: usize| {
+        let offset = usize::try_from(addr - ATOMIC_MEM_BASE).unwrap();
+        assert_eq!(offset % bytes, 0, "misaligned access at {addr:#x}");
+        let mut value = [0u8; 8];
+        value[..bytes].copy_from_slice(&mem[offset..][..bytes]);
+        u64::from_le_bytes(value)
+    };
+    let mut pc = 0;
+    let mut steps = 0;
+    while pc < code.len() {
+        steps += 1;
+        assert!(steps < 1000, "no progress at {pc:#x}");
+        let w = u32::from_le_bytes(code[pc..][..4].try_into().unwrap());
+        let rd = ((w >> 7) & 0x1f) as usize;
+        let rs1 = x[((w >> 15) & 0x1f) as usize];
+        let rs2 = x[((w >> 20) & 0x1f) as usize];
+        let imm_i = i64::from((w as i32) >> 20);
+        let shamt = (w >> 20) & 0x3f;
+        let funct7 = w >> 25;
+        let sext32 = |v: u64| i64::from(v as i32) as u64;
+        let mut next_pc = pc + 4;
+        x[rd] = match (w & 0x7f, (w >> 12) & 0x7) {
+            (0x13, 0) => rs1.wrapping_add_signed(imm_i),
+            (0x13, 1) => rs1 << shamt,
+            (0x13, 4) => rs1 ^ imm_i as u64,
+            (0x13, 5) if funct7 >> 1 == 0 => rs1 >> shamt,
+            (0x13, 5) if funct7 >> 1 == 0b010000 => ((rs1 as i64) >> shamt) as u64,
+            (0x13, 6) => rs1 | imm_i as u64,
+            (0x13, 7) => rs1 & imm_i as u64,
+            (0x1b, 0) => sext32(rs1.wrapping_add_signed(imm_i)),
+            (0x33, 0) if funct7 == 0 => rs1.wrapping_add(rs2),
+            (0x33, 0) if funct7 == 0b0100000 => rs1.wrapping_sub(rs2),
+            (0x33, 1) if funct7 == 0 => rs1 << (rs2 & 0x3f),
+            (0x33, 2) if funct7 == 0 => u64::from((rs1 as i64) < (rs2 as i64)),
+            (0x33, 3) if funct7 == 0 => u64::from(rs1 < rs2),
+            (0x33, 4) if funct7 == 0 => rs1 ^ rs2,
+            (0x33, 5) if funct7 == 0 => rs1 >> (rs2 & 0x3f),
+            (0x33, 5) if funct7 == 0b0100000 => ((rs1 as i64) >> (rs2 & 0x3f)) as u64,
+            (0x33, 6) if funct7 == 0 => rs1 | rs2,
+            (0x33, 7) if funct7 == 0 => rs1 & rs2,
+            (0x3b, 0) if funct7 == 0 => sext32(rs1.wrapping_add(rs2)),
+            (0x3b, 0) if funct7 == 0b0100000 => sext32(rs1.wrapping_sub(rs2)),
+            // lr.w, lr.d
+            (0x2f, f3 @ (2 | 3)) if w >> 27 == 0b00010 => {
+                assert_eq!((w >> 20) & 0x1f, 0, "lr with rs2 at {pc:#x}");
+                match f3 {
+                    2 => sext32(word(mem, rs1, 4)),
+                    _ => word(mem, rs1, 8),
+                }
+            }
+            // sc.w, sc.d
+            (0x2f, f3 @ (2 | 3)) if w >> 27 == 0b00011 => {
+                let bytes = if f3 == 2 { 4 } else { 8 };
+                let _ = word(mem, rs1, bytes);
+                let offset = usize::try_from(rs1 - ATOMIC_MEM_BASE).unwrap();
+                mem[offset..][..bytes].copy_from_slice(&rs2.to_le_bytes()[..bytes]);
+                0
+            }
+            // beq, bne, blt, bge, bltu, bgeu
+            (0x63, f3) => {
+                let imm = ((w >> 31) << 12)
+                    | (((w >> 7) & 1) << 11)
+                    | (((w >> 25) & 0x3f) << 5)
+                    | (((w >> 8) & 0xf) << 1);
+                let imm = ((imm << 19) as i32) >> 19;
+                let taken = match f3 {
+                    0 => rs1 == rs2,
+                    1 => rs1 != rs2,
+                    4 => (rs1 as i64) < (rs2 as i64),
+                    5 => (rs1 as i64) >= (rs2 as i64),
+                    6 => rs1 < rs2,
+                    7 => rs1 >= rs2,
+                    _ => panic!("unexpected branch {w:#010x} at {pc:#x}"),
+                };
+                if taken {
+                    next_pc = pc.wrapping_add_signed(imm as isize);
+                }
+                x[rd]
+            }
+            // jal
+            (0x6f, _) => {
+                let imm = ((w >> 31) << 20)
+                    | (((w >> 12) & 0xff) << 12)
+                    | (((w >> 20) & 1) << 11)
+                    | (((w >> 21) & 0x3ff) << 1);
+                next_pc = pc.wrapping_add_signed((((imm << 11) as i32) >> 11) as isize);
+                (pc + 4) as u64
+            }
+            _ => panic!("unexpected instruction {w:#010x} at {pc:#x}"),
+        };
+        x[0] = 0;
+        pc = next_pc;
+    }
+}
+
+#[test]
+fn riscv64_atomic_sequences_under_every_ordering() {
+    use crate::ir::AtomicRmwOp;
+
+    let (flags, isa_flags) = make_test_flags();
+    let emit_info = EmitInfo::new(flags, isa_flags);
+    let emit = |inst: Inst| {
+        let mut buffer = MachBuffer::new();
+        inst.emit(&mut buffer, &emit_info, &mut Default::default());
+        let buffer = buffer.finish(&Default::default(), &mut Default::default());
+        buffer.data().to_vec()
+    };
+
+    // The memory word holds `OLD` at the start of the accessed field, with
+    // the surrounding bytes set so that clobbering them shows.
+    const OLD: u64 = 0x8123_4567_89ab_cdef;
+    const OPERAND: u64 = 0x7edc_ba98_7654_3210;
+    let setup = |ty: Type, old: u64| {
+        let bytes = ty.bytes() as usize;
+        // Put subword fields in the middle of their word.
+        let field = if bytes < 4 { 4 - bytes } else { 0 };
+        let mut mem = [0x5a; 16];
+        mem[field..][..bytes].copy_from_slice(&old.to_le_bytes()[..bytes]);
+        let mut x = [0u64; 32];
+        x[11] = ATOMIC_MEM_BASE;
+        x[12] = u64::try_from(field * 8).unwrap();
+        (field, mem, x)
+    };
+    let field_value = |mem: &[u8; 16], field: usize, ty: Type| {
+        let mut value = [0u8; 8];
+        value[..ty.bytes() as usize].copy_from_slice(&mem[field..][..ty.bytes() as usize]);
+        u64::from_le_bytes(value)
+    };
+    let check_rest = |mem: &[u8; 16], field: usize, ty: Type| {
+        for (i, byte) in mem.iter().enumerate() {
+            if i < field || i >= field + ty.bytes() as usize {
+                assert_eq!(*byte, 0x5a, "byte {i} clobbered");
+            }
+        }
+    };
+
+    for amo in [AMO::Relax, AMO::Acquire, AMO::Release, AMO::SeqCst] {
+        // Sub-word operations go through a loop for every operation, and wider
+        // ones only for `nand`.
+        let subword_ops = [
+            AtomicRmwOp::Add,
+            AtomicRmwOp::Sub,
+            AtomicRmwOp::And,
+            AtomicRmwOp::Nand,
+            AtomicRmwOp::Or,
+            AtomicRmwOp::Xor,
+            AtomicRmwOp::Xchg,
+            AtomicRmwOp::Umin,
+            AtomicRmwOp::Umax,
+            AtomicRmwOp::Smin,
+            AtomicRmwOp::Smax,
+        ];
+        let cases = [I8, I16]
+            .into_iter()
+            .flat_map(|ty| subword_ops.map(|op| (ty, op)))
+            .chain([(I32, AtomicRmwOp::Nand), (I64, AtomicRmwOp::Nand)]);
+        for (ty, op) in cases {
+            for (old, operand) in [(OLD, OPERAND), (OPERAND, OLD)] {
+                let bits = ty.bits();
+                let mask = u64::MAX >> (64 - bits);
+                let sext = |v: u64| (((v << (64 - bits)) as i64) >> (64 - bits)) as u64;
+                let subword = bits < 32;
+                let (field, mut mem, mut x) = setup(ty, old);
+                // Lowering sign-extends the operand of sub-word signed
+                // comparisons and zero-extends it otherwise.
+                x[13] = match op {
+                    AtomicRmwOp::Smin | AtomicRmwOp::Smax if subword => sext(operand),
+                    _ if subword => operand & mask,
+                    _ => operand,
+                };
+                let code = emit(Inst::AtomicRmwLoop {
+                    offset: if subword { x_reg(12) } else { zero_reg() },
+                    op,
+                    dst: writable_a0(),
+                    ty,
+                    p: a1(),
+                    x: x_reg(13),
+                    t0: Writable::from_reg(x_reg(14)),
+                    word: if subword {
+                        Writable::from_reg(x_reg(15))
+                    } else {
+                        writable_zero_reg()
+                    },
+                    amo,
+                });
+                run_atomic_sequence(&code, &mut x, &mut mem);
+
+                let (a, b) = (old & mask, operand & mask);
+                let expected = match op {
+                    AtomicRmwOp::Add => a.wrapping_add(b),
+                    AtomicRmwOp::Sub => a.wrapping_sub(b),
+                    AtomicRmwOp::And => a & b,
+                    AtomicRmwOp::Nand => !(a & b),
+                    AtomicRmwOp::Or => a | b,
+                    AtomicRmwOp::Xor => a ^ b,
+                    AtomicRmwOp::Xchg => b,
+                    AtomicRmwOp::Umin => a.min(b),
+                    AtomicRmwOp::Umax => a.max(b),
+                    AtomicRmwOp::Smin => (sext(a) as i64).min(sext(b) as i64) as u64,
+                    AtomicRmwOp::Smax => (sext(a) as i64).max(sext(b) as i64) as u64,
+                } & mask;
+                let case = format!("{op:?}.{ty} {amo:?} on {old:#x}, {operand:#x}");
+                assert_eq!(field_value(&mem, field, ty), expected, "{case}");
+                assert_eq!(x[10] & mask, a, "{case}");
+                check_rest(&mem, field, ty);
+            }
+        }
+
+        for ty in [I8, I16, I32, I64] {
+            let mask = u64::MAX >> (64 - ty.bits());
+            for (expected, swaps) in [(OLD, true), (OPERAND, false)] {
+                let (field, mut mem, mut x) = setup(ty, OLD);
+                x[13] = expected & mask;
+                x[14] = OPERAND;
+                let code = emit(Inst::AtomicCas {
+                    offset: if ty.bits() < 32 {
+                        x_reg(12)
+                    } else {
+                        zero_reg()
+                    },
+                    t0: Writable::from_reg(x_reg(15)),
+                    dst: writable_a0(),
+                    e: x_reg(13),
+                    addr: a1(),
+                    v: x_reg(14),
+                    ty,
+                    amo,
+                });
+                run_atomic_sequence(&code, &mut x, &mut mem);
+
+                let case = format!("cas.{ty} {amo:?} expecting {expected:#x}");
+                let stored = if swaps { OPERAND } else { OLD };
+                assert_eq!(field_value(&mem, field, ty), stored & mask, "{case}");
+                assert_eq!(x[10] & mask, OLD & mask, "{case}");
+                check_rest(&mem, field, ty);
+            }
+        }
+    }
+}
+
+#[test]
+fn riscv64_load_ext_name_relocations() {
+    use crate::binemit::Reloc;
+    use crate::ir::ExternalName;
+    use crate::machinst::buffer::FinalizedRelocTarget;
+    use crate::settings::Configurable;
+
+    let (flags, isa_flags) = make_test_flags();
+    let emit_info = EmitInfo::new(flags, isa_flags.clone());
+    let mut b = settings::builder();
+    b.enable("is_pic").unwrap();
+    let pic_emit_info = EmitInfo::new(settings::Flags::new(b), isa_flags);
+
+    let name = ExternalName::testcase("sym");
+    let emit = |emit_info: &EmitInfo, distance: RelocDistance, offset: i64| {
+        let mut buffer = MachBuffer::new();
+        Inst::LoadExtName {
+            rd: writable_a0(),
+            name: Box::new(name.clone()),
+            offset,
+            distance,
+        }
+        .emit(&mut buffer, emit_info, &mut Default::default());
+        let buffer = buffer.finish(&Default::default(), &mut Default::default());
+        let words = buffer
+            .data()
+            .chunks(4)
+            .map(|c| u32::from_le_bytes(c.try_into().unwrap()))
+            .collect::<Vec<_>>();
+        let relocs = buffer
+            .relocs()
+            .iter()
+            .map(|r| (r.offset, r.kind, r.target.clone(), r.addend))
+            .collect::<Vec<_>>();
+        (words, relocs)
+    };
+    let sym = FinalizedRelocTarget::ExternalName(name.clone());
+
+    // Near names are addressed pc-relative with or without PIC, with the
+    // offset folded into the addend of the `auipc` relocation:
+    //   auipc a0,0       # R_RISCV_PCREL_HI20 (sym + 16)
+    //   addi a0,a0,0     # R_RISCV_PCREL_LO12_I (auipc)
+    for emit_info in [&emit_info, &pic_emit_info] {
+        assert_eq!(
+            emit(emit_info, RelocDistance::Near, 16),
+            (
+                vec![0x00000517, 0x00050513],
+                vec![
+                    (0, Reloc::RiscvPCRelHi20, sym.clone(), 16),
+                    (4, Reloc::RiscvPCRelLo12I, FinalizedRelocTarget::Func(0), 0),
+                ]
+            )
+        );
+    }
+
+    // Far names under PIC go through the GOT:
+    //   auipc a0,0       # R_RISCV_GOT_HI20 (sym)
+    //   ld a0,0(a0)      # R_RISCV_PCREL_LO12_I (auipc)
+    assert_eq!(
+        emit(&pic_emit_info, RelocDistance::Far, 0),
+        (
+            vec![0x00000517, 0x00053503],
+            vec![
+                (0, Reloc::RiscvGotHi20, sym.clone(), 0),
+                (4, Reloc::RiscvPCRelLo12I, FinalizedRelocTarget::Func(0), 0),
+            ]
+        )
+    );
+
+    // Far names without PIC fall back to an absolute address literal.
+    let (_, relocs) = emit(&emit_info, RelocDistance::Far, 16);
+    assert!(
+        matches!(
+            &relocs[..],
+            [(_, Reloc::Abs8, target, 16)] if *target == sym
+        ),
+        "{relocs:?}"
+    );
+}
+
+#[test]
+fn riscv64_br_table_huge() {
+    let (flags, isa_flags) = make_test_flags();
+    let emit_info = EmitInfo::new(flags, isa_flags);
+
+    // A table this large is well outside of the range of `B12` and `Imm12`,
+    // so every entry has to be reached through its 32-bit offset.
+    let n = 5000;
+    let mut buffer = MachBuffer::new();
+    let mut state = EmitState::default();
+    let targets = (0..=n).map(|_| buffer.get_label()).collect::<Vec<_>>();
+    Inst::BrTable {
+        index: a0(),
+        tmp1: writable_a1(),
+        tmp2: Writable::from_reg(a2()),
+        targets: targets.clone(),
+    }
+    .emit(&mut buffer, &emit_info, &mut state);
+    let table_end = buffer.cur_offset();
+    let table_start = table_end - 4 * n;
+    assert_eq!(table_start % 4, 0);
+
+    let mut label_offsets = vec![];
+    for label in targets.iter() {
+        label_offsets.push(buffer.cur_offset());
+        buffer.bind_label(*label, &mut Default::default());
+        Inst::Nop4.emit(&mut buffer, &emit_info, &mut state);
+    }
+    let buffer = buffer.finish(&Default::default(), &mut Default::default());
+    let data = buffer.data();
+
+    // The dispatch ends with `jalr zero, tmp1, table_offset`, right before
+    // the table.
+    let jalr = u32::from_le_bytes(data[table_start as usize - 4..][..4].try_into().unwrap());
+    assert_eq!(jalr & 0xfffff, 0x58067, "{jalr:#x}");
+
+    // Each entry holds the offset of its target from the start of the table.
+    for (i, target) in label_offsets[1..].iter().enumerate() {
+        let entry = table_start as usize + 4 * i;
+        let offset = i32::from_le_bytes(data[entry..][..4].try_into().unwrap());
+        assert_eq!(
+            table_start.wrapping_add_signed(offset),
+            *target,
+            "entry {i}"
+        );
+    }
+}
+
+/// Emits a `BrTable` with `n` entries and returns the code along with the
+/// offsets of the default target and of each entry's target.
+fn emit_br_table(emit_info: &EmitInfo, n: u32) -> (Vec<u8>, Vec<u32>) {
+    let mut buffer = MachBuffer::new();
+    let mut state = EmitState::default();
+    let targets = (0..=n).map(|_| buffer.get_label()).collect::<Vec<_>>();
+    Inst::BrTable {
+        index: a0(),
+        tmp1: writable_a1(),
+        tmp2: Writable::from_reg(a2()),
+        targets: targets.clone(),
+    }
+    .emit(&mut buffer, emit_info, &mut state);
+
+    let mut label_offsets = vec![];
+    for label in targets.iter() {
+        label_offsets.push(buffer.cur_offset());
+        buffer.bind_label(*label, &mut Default::default());
+        Inst::Nop4.emit(&mut buffer, emit_info, &mut state);
+    }
+    let buffer = buffer.finish(&Default::default(), &mut Default::default());
+    (buffer.data().to_vec(), label_offsets)
+}
+
+/// Runs `code` from its start with `a0` set to the given value and returns the
+/// first offset in `exits` that it reaches.
+///
+/// Only the handful of instructions that branches, veneers and `BrTable`
+/// dispatch sequences are made of are supported.
+fn run_until_exit(code: &[u8], exits: &[u32], a0: u64) -> u32 {
+    let mut x = [0u64; 32];
+    x[10] = a0;
+    let mut pc = 0u32;
+    while !exits.contains(&pc) {
+        let w = u32::from(u16::from_le_bytes(
+            code[pc as usize..][..2].try_into().unwrap(),
+        ));
+        if w & 0b11 != 0b11 {
+            pc = match w {
+                // c.nop
+                0x0001 => pc + 2,
+                // c.j
+                _ if w & 0xe003 == 0xa001 => {
+                    let imm = (((w >> 12) & 1) << 11)
+                        | (((w >> 11) & 1) << 4)
+                        | (((w >> 9) & 0b11) << 8)
+                        | (((w >> 8) & 1) << 10)
+                        | (((w >> 7) & 1) << 6)
+                        | (((w >> 6) & 1) << 7)
+                        | (((w >> 3) & 0b111) << 1)
+                        | (((w >> 2) & 1) << 5);
+                    pc.wrapping_add_signed(((imm << 20) as i32) >> 20)
+                }
+                _ => panic!("unexpected instruction {w:#06x} at {pc:#x}"),
+            };
+            continue;
+        }
+        let w = u32::from_le_bytes(code[pc as usize..][..4].try_into().unwrap());
+        let rd = ((w >> 7) & 0x1f) as usize;
+        let rs1 = x[((w >> 15) & 0x1f) as usize];
+        let rs2 = x[((w >> 20) & 0x1f) as usize];
+        let imm_i = i64::from((w as i32) >> 20);
+        let imm_u = i64::from((w & 0xfffff000) as i32);
+        let mut next_pc = pc + 4;
+        match (w & 0x7f, (w >> 12) & 0x7) {
+            // addi, slli, srli
+            (0x13, 0) => x[rd] = rs1.wrapping_add_signed(imm_i),
+            (0x13, 1) => x[rd] = rs1 << ((w >> 20) & 0x3f),
+            (0x13, 5) if w >> 26 == 0 => x[rd] = rs1 >> ((w >> 20) & 0x3f),
+            // addiw
+            (0x1b, 0) => x[rd] = i64::from(rs1.wrapping_add_signed(imm_i) as i32) as u64,
+            // lui, auipc
+            (0x37, _) => x[rd] = imm_u as u64,
+            (0x17, _) => x[rd] = u64::from(pc).wrapping_add_signed(imm_u),
+            // add
+            (0x33, 0) if w >> 25 == 0 => x[rd] = rs1.wrapping_add(rs2),
+            // lw
+            (0x03, 2) => {
+                let addr = rs1.wrapping_add_signed(imm_i) as usize;
+                let value = i32::from_le_bytes(code[addr..][..4].try_into().unwrap());
+                x[rd] = i64::from(value) as u64;
+            }
+            // beq, bne, bltu
+            (0x63, f3 @ (0 | 1 | 6)) => {
+                let imm = ((w >> 31) << 12)
+                    | (((w >> 7) & 1) << 11)
+                    | (((w >> 25) & 0x3f) << 5)
+                    | (((w >> 8) & 0xf) << 1);
+                let imm = ((imm << 19) as i32) >> 19;
+                let taken = match f3 {
+                    0 => rs1 == rs2,
+                    1 => rs1 != rs2,
+                    _ => rs1 < rs2,
+                };
+                if taken {
+                    next_pc = pc.wrapping_add_signed(imm);
+                }
+            }
+            // jal
+            (0x6f, _) => {
+                let imm = ((w >> 31) << 20)
+                    | (((w >> 12) & 0xff) << 12)
+                    | (((w >> 20) & 1) << 11)
+                    | (((w >> 21) & 0x3ff) << 1);
+                x[rd] = u64::from(pc + 4);
+                next_pc = pc.wrapping_add_signed(((imm << 11) as i32) >> 11);
+            }
+            // jalr
+            (0x67, 0) => {
+                x[rd] = u64::from(pc + 4);
+                next_pc = (rs1.wrapping_add_signed(imm_i) & !1) as u32;
+            }
+            _ => panic!("unexpected instruction {w:#010x} at {pc:#x}"),
+        }
+        x[0] = 0;
+        pc = next_pc;
+    }
+    pc
+}
+
+#[test]
+fn riscv64_br_table_dispatch() {
+    use crate::settings::Configurable;
+
+    let (flags, isa_flags) = make_test_flags();
+    let jump_table_info = EmitInfo::new(flags, isa_flags.clone());
+    let mut b = settings::builder();
+    b.set("enable_jump_tables", "false").unwrap();
+    let branch_tree_info = EmitInfo::new(settings::Flags::new(b), isa_flags);
+
+    // Both the jump table and the branch tree pick the same target for every
+    // index, and out-of-range indices go to the default target. Only the low
+    // 32 bits of the index are significant.
+    for n in [0, 1, 2, 3, 4, 7, 100, 1000] {
+        for (emit_info, mode) in [
+            (&jump_table_info, "jump table"),
+            (&branch_tree_info, "branch tree"),
+        ] {
+            let (code, targets) = emit_br_table(emit_info, n);
+            let indices = (0..n + 2).chain([u32::MAX - 1, u32::MAX]);
+            for index in indices {
+                let expected = targets.get(index as usize + 1).copied();
+                let expected = expected.unwrap_or(targets[0]);
+                for high in [0, 0xdead_beef] {
+                    let reg = (high << 32) | u64::from(index);
+                    assert_eq!(
+                        run_until_exit(&code, &targets, reg),
+                        expected,
+                        "{mode}: n = {n}, index = {reg:#x}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Emits `inst`, preceded by an island if one is needed.
+fn emit_with_island(buffer: &mut MachBuffer<Inst>, emit_info: &EmitInfo, inst: Inst) {
+    if buffer.island_needed(Inst::worst_case_size()) {
+        buffer.emit_island(Inst::worst_case_size(), &mut Default::default());
+    }
+    inst.emit(buffer, emit_info, &mut EmitState::default());
+}
+
+/// Emits a conditional branch on `a0 != 0` followed by a jump, with `distance`
+/// bytes of nops between them and their targets, and returns the code along
+/// with the offsets of the taken and the not-taken target.
+///
+/// With `backward` the targets come first, and the code starts with a jump
+/// over the nops to the branches.
+fn emit_far_branches(emit_info: &EmitInfo, distance: u32, backward: bool) -> (Vec<u8>, [u32; 2]) {
+    let mut buffer = MachBuffer::new();
+    let entry = buffer.get_label();
+    let taken = buffer.get_label();
+    let not_taken = buffer.get_label();
+    let mut targets = [0; 2];
+
+    let emit_branches = |buffer: &mut MachBuffer<Inst>| {
+        buffer.bind_label(entry, &mut Default::default());
+        let kind = IntegerCompare {
+            kind: IntCC::NotEqual,
+            rs1: a0(),
+            rs2: zero_reg(),
+        };
+        let branch = Inst::CondBr {
+            taken: CondBrTarget::Label(taken),
+            not_taken: CondBrTarget::Label(not_taken),
+            kind,
+        };
+        emit_with_island(buffer, emit_info, branch);
+    };
+    let mut emit_targets = |buffer: &mut MachBuffer<Inst>| {
+        for (i, label) in [taken, not_taken].into_iter().enumerate() {
+            buffer.bind_label(label, &mut Default::default());
+            targets[i] = buffer.cur_offset();
+            emit_with_island(buffer, emit_info, Inst::Nop4);
+        }
+    };
+
+    if backward {
+        emit_with_island(&mut buffer, emit_info, Inst::gen_jump(entry));
+        emit_targets(&mut buffer);
+    } else {
+        emit_branches(&mut buffer);
+    }
+    let end = buffer.cur_offset() + distance;
+    while buffer.cur_offset() < end {
+        emit_with_island(&mut buffer, emit_info, Inst::Nop4);
+    }
+    if backward {
+        emit_branches(&mut buffer);
+    } else {
+        emit_targets(&mut buffer);
+    }
+
+    let buffer = buffer.finish(&Default::default(), &mut Default::default());
+    (buffer.data().to_vec(), targets)
+}
+
+#[test]
+fn riscv64_far_branches() {
+    use crate::settings::Configurable;
+
+    let (flags, isa_flags) = make_test_flags();
+    let mut b = super::super::riscv_settings::builder();
+    b.enable("has_zca").unwrap();
+    let compressed_isa_flags = super::super::riscv_settings::Flags::new(&flags, &b);
+
+    // Branches and jumps whose targets are beyond the reach of `b<cond>`
+    // (4 KiB), `c.j` (2 KiB) or `jal` (1 MiB), in either direction, are
+    // routed through veneers and still reach their targets.
+    for (isa_flags, has_zca) in [(isa_flags, false), (compressed_isa_flags, true)] {
+        let emit_info = EmitInfo::new(flags.clone(), isa_flags);
+        for distance in [0, 2040, 4090, 4100, (1 << 20) - 8, (1 << 20) + 8, 3 << 20] {
+            for backward in [false, true] {
+                let (code, [taken, not_taken]) = emit_far_branches(&emit_info, distance, backward);
+                let context =
+                    format!("distance = {distance}, backward = {backward}, has_zca = {has_zca}");
+                assert_eq!(
+                    run_until_exit(&code, &[taken, not_taken], 1),
+                    taken,
+                    "{context}"
+                );
+                assert_eq!(
+                    run_until_exit(&code, &[taken, not_taken], 0),
+                    not_taken,
+                    "{context}"
+                );
+            }
+        }
+    }
+}
+
+/// Emits a branch to a label `distance` bytes away, with `nops` nops and then
+/// `candidates` in between, checking for islands before each instruction like
+/// `VCode::emit` does.
+fn emit_branch_over_large_instructions(
+    emit_info: &EmitInfo,
+    candidates: &[Inst],
+    nops: usize,
+    far_jump: bool,
+    distance: u32,
+) {
+    let mut buffer = MachBuffer::new();
+    let label = buffer.get_label();
+    let branch = if far_jump {
+        Inst::gen_jump(label)
+    } else {
+        Inst::CondBr {
+            taken: CondBrTarget::Label(label),
+            not_taken: CondBrTarget::Fallthrough,
+            kind: IntegerCompare {
+                kind: IntCC::Equal,
+                rs1: a0(),
+                rs2: a1(),
+            },
+        }
+    };
+    emit_with_island(&mut buffer, emit_info, branch);
+    for _ in 0..nops {
+        emit_with_island(&mut buffer, emit_info, Inst::Nop4);
+    }
+    let end = buffer.cur_offset() + distance;
+    for inst in candidates.iter().cycle() {
+        if buffer.cur_offset() >= end {
+            break;
+        }
+        emit_with_island(&mut buffer, emit_info, inst.clone());
+    }
+    buffer.bind_label(label, &mut Default::default());
+    buffer.finish(&Default::default(), &mut Default::default());
+}
+
+#[test]
+fn riscv64_islands_between_large_instructions() {
+    use crate::settings::Configurable;
+
+    let mut b = settings::builder();
+    b.enable("is_pic").unwrap();
+    let flags = settings::Flags::new(b);
+    let isa_flags =
+        super::super::riscv_settings::Flags::new(&flags, &super::super::riscv_settings::builder());
+    let mut b = super::super::riscv_settings::builder();
+    b.enable("has_zca").unwrap();
+    let compressed_isa_flags = super::super::riscv_settings::Flags::new(&flags, &b);
+
+    // The island for the branch is only emitted shortly before its deadline.
+    // If `Inst::worst_case_size` were too small, some instruction could
+    // straddle the deadline, and patching the branch would panic. Shifting
+    // the instructions by a few nops moves the deadline around within them.
+    let candidates = worst_case_size_candidates();
+    for isa_flags in [isa_flags, compressed_isa_flags] {
+        let emit_info = EmitInfo::new(flags.clone(), isa_flags);
+        for nops in 0..Inst::worst_case_size() as usize {
+            for far_jump in [false, true] {
+                emit_branch_over_large_instructions(
+                    &emit_info,
+                    &candidates,
+                    nops,
+                    far_jump,
+                    8 << 10,
+                );
+            }
+        }
+        // Also jump over more than the 1 MiB reach of `jal`.
+        emit_branch_over_large_instructions(&emit_info, &candidates, 0, true, 3 << 20);
+    }
+}
+
+#[test]
+fn riscv64_constant_pool_loads() {
+    use crate::machinst::{VCodeConstantData, VCodeConstants};
+
+    let (flags, isa_flags) = make_test_flags();
+    let emit_info = EmitInfo::new(flags, isa_flags);
+
+    // Inserting a value twice hands back the same constant.
+    let values = [0x0123_4567_89ab_cdef_u64, 0xfedc_ba98_7654_3210];
+    let mut constants = VCodeConstants::default();
+    let order = [0, 1, 0, 1, 0];
+    let uses: Vec<_> = order
+        .iter()
+        .map(|&i| constants.insert(VCodeConstantData::U64(values[i].to_le_bytes())))
+        .collect();
+    assert_eq!(constants.len(), values.len());
+
+    let mut buffer = MachBuffer::new();
+    buffer.register_constants(&constants);
+    // A leading nop leaves the pool misaligned unless it's padded.
+    Inst::Nop4.emit(&mut buffer, &emit_info, &mut Default::default());
+    for &constant in &uses {
+        Inst::Load {
+            rd: writable_a0(),
+            op: LoadOP::Ld,
+            flags: MemFlags::trusted(),
+            from: AMode::Const(constant),
+        }
+        .emit(&mut buffer, &emit_info, &mut Default::default());
+    }
+    let buffer = buffer.finish(&constants, &mut Default::default());
+    let data = buffer.data();
+
+    let word = |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+    for (i, &value) in order.iter().enumerate() {
+        // Each load is an `auipc` and an `ld` relative to it.
+        let auipc = 4 + 8 * i;
+        assert_eq!(word(auipc) & 0x7f, 0x17);
+        assert_eq!(word(auipc + 4) & 0x707f, 0x3003);
+        let hi = i64::from((word(auipc) & 0xffff_f000) as i32);
+        let lo = i64::from(word(auipc + 4) as i32 >> 20);
+        let addr = usize::try_from(auipc as i64 + hi + lo).unwrap();
+        assert_eq!(addr % 8, 0);
+        let loaded = u64::from_le_bytes(data[addr..addr + 8].try_into().unwrap());
+        assert_eq!(loaded, values[value]);
+    }
+
+    // Each value is emitted once, after the loads.
+    for value in values {
+        let bytes = value.to_le_bytes();
+        assert_eq!(data.windows(8).filter(|w| *w == bytes).count(), 1);
+    }
+    assert_eq!(data.len(), 4 + 8 * uses.len() + 4 + 8 * values.len());
+}
+
+// Major opcodes from the "RV32/64G Instruction Set Listings" of the ISA manual.
+const OP: u32 = 0b0110011;
+const OP_32: u32 = 0b0111011;
+const OP_IMM: u32 = 0b0010011;
+const OP_IMM_32: u32 = 0b0011011;
+const OP_FP: u32 = 0b1010011;
+
+fn r_type(opcode: u32, rd: u32, funct3: u32, rs1: u32, rs2: u32, funct7: u32) -> u32 {
+    opcode | rd << 7 | funct3 << 12 | rs1 << 15 | rs2 << 20 | funct7 << 25
+}
+
+fn i_type(opcode: u32, rd: u32, funct3: u32, rs1: u32, imm: u32) -> u32 {
+    opcode | rd << 7 | funct3 << 12 | rs1 << 15 | (imm & 0xfff) << 20
+}
+
+/// Builds a table with a golden entry for every variant of an opcode enum.
+///
+/// The exhaustive `match` turns a variant without an entry into a compile
+/// error, so new opcodes can't be added without their golden encoding.
+macro_rules! golden_table {
+    ($op:ident { $($variant:ident => $golden:expr,)* }) => {{
+        fn _every_variant_has_an_entry(op: $op) {
+            match op {
+                $($op::$variant => {})*
+            }
+        }
+        [$(($op::$variant, $golden)),*]
+    }};
+}
+
+/// Register numbers and an immediate to encode each opcode with. The first
+/// set avoids `x0` and repeated registers, which disassemblers print as
+/// aliases, and is the one cross-checked against Capstone.
+const GOLDEN_OPERANDS: [(u32, u32, u32, i16); 4] = [
+    (10, 11, 12, 0x123),
+    (31, 30, 29, -2048),
+    (1, 0, 31, 2047),
+    (5, 5, 6, -1),
+];
+
+/// Extensions whose instructions Capstone decodes.
+const CAPSTONE_EXTENSIONS: [&str; 3] = ["i", "m", "f"];
+
+/// Register class of an FPU instruction operand.
+#[derive(Clone, Copy, PartialEq)]
+enum Class {
+    X,
+    F,
+}
+
+impl Class {
+    fn reg(self, num: u32) -> Reg {
+        match self {
+            Class::X => x_reg(num as usize),
+            Class::F => f_reg(num as usize),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct AluRRRGolden {
+    name: &'static str,
+    ext: &'static str,
+    opcode: u32,
+    funct3: u32,
+    funct7: u32,
+    /// The instruction is `name` with its sources swapped, e.g. `sgt`.
+    swapped: bool,
+}
+
+fn alu_rrr(
+    name: &'static str,
+    ext: &'static str,
+    opcode: u32,
+    funct7: u32,
+    funct3: u32,
+) -> AluRRRGolden {
+    AluRRRGolden {
+        name,
+        ext,
+        opcode,
+        funct3,
+        funct7,
+        swapped: false,
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ImmField {
+    /// A signed 12-bit immediate.
+    Signed,
+    /// A 6-bit shift amount below `funct6`.
+    Shamt6(u32),
+    /// A 5-bit shift amount below `funct7`.
+    Shamt5(u32),
+    /// A unary operation selected by the whole field.
+    Funct12(u32),
+}
+
+#[derive(Clone, Copy)]
+struct AluRRIGolden {
+    name: &'static str,
+    ext: &'static str,
+    opcode: u32,
+    funct3: u32,
+    imm: ImmField,
+}
+
+fn alu_rri(
+    name: &'static str,
+    ext: &'static str,
+    opcode: u32,
+    funct3: u32,
+    imm: ImmField,
+) -> AluRRIGolden {
+    AluRRIGolden {
+        name,
+        ext,
+        opcode,
+        funct3,
+        imm,
+    }
+}
+
+#[derive(Clone, Copy)]
+struct FpuGolden {
+    /// The mnemonic, with `{}` standing for the format letter and `{x}` for
+    /// the letter `fmv` uses instead.
+    name: &'static str,
+    ext: &'static str,
+    funct5: u32,
+    /// The `rs2` field of a unary operation.
+    rs2: u32,
+    /// A fixed `funct3`, or `None` if it holds the rounding mode.
+    funct3: Option<u32>,
+    rd: Class,
+    rs: Class,
+    widths: &'static [FpuOPWidth],
+}
+
+const ALL_WIDTHS: &[FpuOPWidth] = &[FpuOPWidth::S, FpuOPWidth::D, FpuOPWidth::H];
+
+fn fpu(name: &'static str, ext: &'static str, funct5: u32, funct3: Option<u32>) -> FpuGolden {
+    FpuGolden {
+        name,
+        ext,
+        funct5,
+        rs2: 0,
+        funct3,
+        rd: Class::F,
+        rs: Class::F,
+        widths: ALL_WIDTHS,
+    }
+}
+
+impl FpuGolden {
+    fn rs2(self, rs2: u32) -> Self {
+        Self { rs2, ..self }
+    }
+
+    fn regs(self, rd: Class, rs: Class) -> Self {
+        Self { rd, rs, ..self }
+    }
+
+    fn widths(self, widths: &'static [FpuOPWidth]) -> Self {
+        Self { widths, ..self }
+    }
+
+    fn name(self, width: FpuOPWidth) -> String {
+        let (fmt, fmv) = match width {
+            FpuOPWidth::S => ("s", "w"),
+            FpuOPWidth::D => ("d", "d"),
+            FpuOPWidth::H => ("h", "h"),
+            FpuOPWidth::Q => ("q", "q"),
+        };
+        self.name.replace("{x}", fmv).replace("{}", fmt)
+    }
+
+    /// Capstone only knows the single- and double-precision instructions.
+    fn in_capstone(self, width: FpuOPWidth) -> bool {
+        CAPSTONE_EXTENSIONS.contains(&self.ext) && matches!(width, FpuOPWidth::S | FpuOPWidth::D)
+    }
+}
+
+fn golden_fmt(width: FpuOPWidth) -> u32 {
+    match width {
+        FpuOPWidth::S => 0b00,
+        FpuOPWidth::D => 0b01,
+        FpuOPWidth::H => 0b10,
+        FpuOPWidth::Q => 0b11,
+    }
+}
+
+/// Rounding modes to encode operations that take one with, and their bits.
+const GOLDEN_FRMS: [(FRM, u32); 4] = [
+    (FRM::RNE, 0b000),
+    (FRM::RTZ, 0b001),
+    (FRM::RUP, 0b011),
+    (FRM::RMM, 0b100),
+];
+
+/// The `FRM` that the emitter places in `funct3` for `bits`.
+fn frm_for_funct3(bits: u32) -> FRM {
+    match bits {
+        0b000 => FRM::RNE,
+        0b001 => FRM::RTZ,
+        0b010 => FRM::RDN,
+        0b011 => FRM::RUP,
+        _ => unreachable!(),
+    }
+}
+
+fn emit_word(emit_info: &EmitInfo, inst: &Inst) -> u32 {
+    let mut buffer = MachBuffer::new();
+    inst.emit(&mut buffer, emit_info, &mut Default::default());
+    let buffer = buffer.finish(&Default::default(), &mut Default::default());
+    let data = buffer.data();
+    assert_eq!(data.len(), 4, "{inst:?}");
+    u32::from_le_bytes(data.try_into().unwrap())
+}
+
+/// Checks that Capstone decodes `word` as `name` with the given operands,
+/// ignoring a trailing rounding mode.
+#[cfg(feature = "disas")]
+fn capstone_check(word: u32, name: &str, regs: &[Reg], imm: Option<i64>) {
+    use capstone::prelude::*;
+    let cs = Capstone::new()
+        .riscv()
+        .mode(arch::riscv::ArchMode::RiscV64)
+        .build()
+        .unwrap();
+    let insns = cs.disasm_all(&word.to_le_bytes(), 0).unwrap();
+    assert_eq!(insns.len(), 1, "{word:#010x} ({name}) does not disassemble");
+    let insn = insns.iter().next().unwrap();
+    assert_eq!(insn.mnemonic(), Some(name), "{word:#010x}");
+
+    let expected: Vec<String> = regs.iter().map(|r| reg_name(*r)).collect();
+    let op_str = insn.op_str().unwrap_or("");
+    let mut actual: Vec<&str> = op_str.split(", ").collect();
+    if let Some(imm) = imm {
+        let last = actual.pop().unwrap();
+        let parsed = match last.strip_prefix('-') {
+            Some(abs) => -parse_capstone_uint(abs),
+            None => parse_capstone_uint(last),
+        };
+        assert_eq!(parsed, imm, "{word:#010x} ({name} {op_str})");
+    } else if actual.len() == expected.len() + 1 {
+        // A static rounding mode.
+        actual.pop();
+    }
+    assert_eq!(actual, expected, "{word:#010x} ({name} {op_str})");
+}
+
+#[cfg(not(feature = "disas"))]
+fn capstone_check(_word: u32, _name: &str, _regs: &[Reg], _imm: Option<i64>) {}
+
+#[cfg(feature = "disas")]
+fn parse_capstone_uint(s: &str) -> i64 {
+    match s.strip_prefix("0x") {
+        Some(hex) => i64::from_str_radix(hex, 16).unwrap(),
+        None => s.parse().unwrap(),
+    }
+}
+
+#[test]
+fn riscv64_alu_rrr_golden_encodings() {
+    let table = golden_table!(AluOPRRR {
+        Add => alu_rrr("add", "i", OP, 0b0000000, 0b000),
+        Sub => alu_rrr("sub", "i", OP, 0b0100000, 0b000),
+        Sll => alu_rrr("sll", "i", OP, 0b0000000, 0b001),
+        Slt => alu_rrr("slt", "i", OP, 0b0000000, 0b010),
+        SltU => alu_rrr("sltu", "i", OP, 0b0000000, 0b011),
+        Sgt => AluRRRGolden {
+            swapped: true,
+            ..alu_rrr("slt", "i", OP, 0b0000000, 0b010)
+        },
+        Sgtu => AluRRRGolden {
+            swapped: true,
+            ..alu_rrr("sltu", "i", OP, 0b0000000, 0b011)
+        },
+        Xor => alu_rrr("xor", "i", OP, 0b0000000, 0b100),
+        Srl => alu_rrr("srl", "i", OP, 0b0000000, 0b101),
+        Sra => alu_rrr("sra", "i", OP, 0b0100000, 0b101),
+        Or => alu_rrr("or", "i", OP, 0b0000000, 0b110),
+        And => alu_rrr("and", "i", OP, 0b0000000, 0b111),
+        Addw => alu_rrr("addw", "i", OP_32, 0b0000000, 0b000),
+        Subw => alu_rrr("subw", "i", OP_32, 0b0100000, 0b000),
+        Sllw => alu_rrr("sllw", "i", OP_32, 0b0000000, 0b001),
+        Srlw => alu_rrr("srlw", "i", OP_32, 0b0000000, 0b101),
+        Sraw => alu_rrr("sraw", "i", OP_32, 0b0100000, 0b101),
+        Mul => alu_rrr("mul", "m", OP, 0b0000001, 0b000),
+        Mulh => alu_rrr("mulh", "m", OP, 0b0000001, 0b001),
+        Mulhsu => alu_rrr("mulhsu", "m", OP, 0b0000001, 0b010),
+        Mulhu => alu_rrr("mulhu", "m", OP, 0b0000001, 0b011),
+        Div => alu_rrr("div", "m", OP, 0b0000001, 0b100),
+        DivU => alu_rrr("divu", "m", OP, 0b0000001, 0b101),
+        Rem => alu_rrr("rem", "m", OP, 0b0000001, 0b110),
+        RemU => alu_rrr("remu", "m", OP, 0b0000001, 0b111),
+        Mulw => alu_rrr("mulw", "m", OP_32, 0b0000001, 0b000),
+        Divw => alu_rrr("divw", "m", OP_32, 0b0000001, 0b100),
+        Divuw => alu_rrr("divuw", "m", OP_32, 0b0000001, 0b101),
+        Remw => alu_rrr("remw", "m", OP_32, 0b0000001, 0b110),
+        Remuw => alu_rrr("remuw", "m", OP_32, 0b0000001, 0b111),
+        Adduw => alu_rrr("add.uw", "zba", OP_32, 0b0000100, 0b000),
+        Sh1add => alu_rrr("sh1add", "zba", OP, 0b0010000, 0b010),
+        Sh1adduw => alu_rrr("sh1add.uw", "zba", OP_32, 0b0010000, 0b010),
+        Sh2add => alu_rrr("sh2add", "zba", OP, 0b0010000, 0b100),
+        Sh2adduw => alu_rrr("sh2add.uw", "zba", OP_32, 0b0010000, 0b100),
+        Sh3add => alu_rrr("sh3add", "zba", OP, 0b0010000, 0b110),
+        Sh3adduw => alu_rrr("sh3add.uw", "zba", OP_32, 0b0010000, 0b110),
+        Andn => alu_rrr("andn", "zbb", OP, 0b0100000, 0b111),
+        Orn => alu_rrr("orn", "zbb", OP, 0b0100000, 0b110),
+        Xnor => alu_rrr("xnor", "zbb", OP, 0b0100000, 0b100),
+        Max => alu_rrr("max", "zbb", OP, 0b0000101, 0b110),
+        Maxu => alu_rrr("maxu", "zbb", OP, 0b0000101, 0b111),
+        Min => alu_rrr("min", "zbb", OP, 0b0000101, 0b100),
+        Minu => alu_rrr("minu", "zbb", OP, 0b0000101, 0b101),
+        Rol => alu_rrr("rol", "zbb", OP, 0b0110000, 0b001),
+        Rolw => alu_rrr("rolw", "zbb", OP_32, 0b0110000, 0b001),
+        Ror => alu_rrr("ror", "zbb", OP, 0b0110000, 0b101),
+        Rorw => alu_rrr("rorw", "zbb", OP_32, 0b0110000, 0b101),
+        Bclr => alu_rrr("bclr", "zbs", OP, 0b0100100, 0b001),
+        Bext => alu_rrr("bext", "zbs", OP, 0b0100100, 0b101),
+        Binv => alu_rrr("binv", "zbs", OP, 0b0110100, 0b001),
+        Bset => alu_rrr("bset", "zbs", OP, 0b0010100, 0b001),
+        Clmul => alu_rrr("clmul", "zbc", OP, 0b0000101, 0b001),
+        Clmulh => alu_rrr("clmulh", "zbc", OP, 0b0000101, 0b011),
+        Clmulr => alu_rrr("clmulr", "zbc", OP, 0b0000101, 0b010),
+        Pack => alu_rrr("pack", "zbkb", OP, 0b0000100, 0b100),
+        Packw => alu_rrr("packw", "zbkb", OP_32, 0b0000100, 0b100),
+        Packh => alu_rrr("packh", "zbkb", OP, 0b0000100, 0b111),
+        CzeroEqz => alu_rrr("czero.eqz", "zicond", OP, 0b0000111, 0b101),
+        CzeroNez => alu_rrr("czero.nez", "zicond", OP, 0b0000111, 0b111),
+    });
+
+    let (flags, isa_flags) = make_test_flags();
+    let emit_info = EmitInfo::new(flags, isa_flags);
+
+    for (alu_op, golden) in table {
+        for (i, &(rd, rs1, rs2, _)) in GOLDEN_OPERANDS.iter().enumerate() {
+            let inst = Inst::AluRRR {
+                alu_op,
+                rd: Writable::from_reg(x_reg(rd as usize)),
+                rs1: x_reg(rs1 as usize),
+                rs2: x_reg(rs2 as usize),
+            };
+            let (src1, src2) = if golden.swapped {
+                (rs2, rs1)
+            } else {
+                (rs1, rs2)
+            };
+            let expected = r_type(golden.opcode, rd, golden.funct3, src1, src2, golden.funct7);
+            let word = emit_word(&emit_info, &inst);
+            assert_eq!(
+                word, expected,
+                "{alu_op:?}: {word:#010x} != {expected:#010x}"
+            );
+
+            if i == 0 && CAPSTONE_EXTENSIONS.contains(&golden.ext) {
+                let regs = [rd, src1, src2].map(|r| x_reg(r as usize));
+                capstone_check(word, golden.name, &regs, None);
+            }
+        }
+    }
+}
+
+#[test]
+fn riscv64_alu_rri_golden_encodings() {
+    use ImmField::*;
+    let table = golden_table!(AluOPRRI {
+        Addi => alu_rri("addi", "i", OP_IMM, 0b000, Signed),
+        Slti => alu_rri("slti", "i", OP_IMM, 0b010, Signed),
+        SltiU => alu_rri("sltiu", "i", OP_IMM, 0b011, Signed),
+        Xori => alu_rri("xori", "i", OP_IMM, 0b100, Signed),
+        Ori => alu_rri("ori", "i", OP_IMM, 0b110, Signed),
+        Andi => alu_rri("andi", "i", OP_IMM, 0b111, Signed),
+        Slli => alu_rri("slli", "i", OP_IMM, 0b001, Shamt6(0b000000)),
+        Srli => alu_rri("srli", "i", OP_IMM, 0b101, Shamt6(0b000000)),
+        Srai => alu_rri("srai", "i", OP_IMM, 0b101, Shamt6(0b010000)),
+        Addiw => alu_rri("addiw", "i", OP_IMM_32, 0b000, Signed),
+        Slliw => alu_rri("slliw", "i", OP_IMM_32, 0b001, Shamt5(0b0000000)),
+        SrliW => alu_rri("srliw", "i", OP_IMM_32, 0b101, Shamt5(0b0000000)),
+        Sraiw => alu_rri("sraiw", "i", OP_IMM_32, 0b101, Shamt5(0b0100000)),
+        SlliUw => alu_rri("slli.uw", "zba", OP_IMM_32, 0b001, Shamt6(0b000010)),
+        Clz => alu_rri("clz", "zbb", OP_IMM, 0b001, Funct12(0x600)),
+        Clzw => alu_rri("clzw", "zbb", OP_IMM_32, 0b001, Funct12(0x600)),
+        Ctz => alu_rri("ctz", "zbb", OP_IMM, 0b001, Funct12(0x601)),
+        Ctzw => alu_rri("ctzw", "zbb", OP_IMM_32, 0b001, Funct12(0x601)),
+        Cpop => alu_rri("cpop", "zbb", OP_IMM, 0b001, Funct12(0x602)),
+        Cpopw => alu_rri("cpopw", "zbb", OP_IMM_32, 0b001, Funct12(0x602)),
+        Sextb => alu_rri("sext.b", "zbb", OP_IMM, 0b001, Funct12(0x604)),
+        Sexth => alu_rri("sext.h", "zbb", OP_IMM, 0b001, Funct12(0x605)),
+        Zexth => alu_rri("zext.h", "zbb", OP_32, 0b100, Funct12(0x080)),
+        Rori => alu_rri("rori", "zbb", OP_IMM, 0b101, Shamt6(0b011000)),
+        Roriw => alu_rri("roriw", "zbb", OP_IMM_32, 0b101, Shamt5(0b0110000)),
+        Rev8 => alu_rri("rev8", "zbb", OP_IMM, 0b101, Funct12(0x6b8)),
+        Brev8 => alu_rri("brev8", "zbkb", OP_IMM, 0b101, Funct12(0x687)),
+        Orcb => alu_rri("orc.b", "zbb", OP_IMM, 0b101, Funct12(0x287)),
+        Bclri => alu_rri("bclri", "zbs", OP_IMM, 0b001, Shamt6(0b010010)),
+        Bexti => alu_rri("bexti", "zbs", OP_IMM, 0b101, Shamt6(0b010010)),
+        Binvi => alu_rri("binvi", "zbs", OP_IMM, 0b001, Shamt6(0b011010)),
+        Bseti => alu_rri("bseti", "zbs", OP_IMM, 0b001, Shamt6(0b001010)),
+    });
+
+    let (flags, isa_flags) = make_test_flags();
+    let emit_info = EmitInfo::new(flags, isa_flags);
+
+    for (alu_op, golden) in table {
+        for (i, &(rd, rs, _, imm)) in GOLDEN_OPERANDS.iter().enumerate() {
+            let (imm, field) = match golden.imm {
+                Signed => (imm, imm as u32),
+                Shamt6(funct6) => {
+                    let shamt = imm & 0x3f;
+                    (shamt, funct6 << 6 | shamt as u32)
+                }
+                Shamt5(funct7) => {
+                    let shamt = imm & 0x1f;
+                    (shamt, funct7 << 5 | shamt as u32)
+                }
+                Funct12(funct12) => (0, funct12),
+            };
+            let inst = Inst::AluRRImm12 {
+                alu_op,
+                rd: Writable::from_reg(x_reg(rd as usize)),
+                rs: x_reg(rs as usize),
+                imm12: Imm12::from_i16(imm),
+            };
+            let expected = i_type(golden.opcode, rd, golden.funct3, rs, field);
+            let word = emit_word(&emit_info, &inst);
+            assert_eq!(
+                word, expected,
+                "{alu_op:?}: {word:#010x} != {expected:#010x}"
+            );
+
+            if i == 0 && CAPSTONE_EXTENSIONS.contains(&golden.ext) {
+                let regs = [rd, rs].map(|r| x_reg(r as usize));
+                capstone_check(word, golden.name, &regs, Some(imm.into()));
+            }
+        }
+    }
+}
+
+#[test]
+fn riscv64_fpu_rr_golden_encodings() {
+    use Class::*;
+    const S: &[FpuOPWidth] = &[FpuOPWidth::S];
+    const D: &[FpuOPWidth] = &[FpuOPWidth::D];
+    let table = golden_table!(FpuOPRR {
+        Fsqrt => fpu("fsqrt.{}", "f", 0b01011, None),
+        Fclass => fpu("fclass.{}", "f", 0b11100, Some(0b001)).regs(X, F),
+        FcvtWFmt => fpu("fcvt.w.{}", "f", 0b11000, None).regs(X, F),
+        FcvtWuFmt => fpu("fcvt.wu.{}", "f", 0b11000, None).rs2(1).regs(X, F),
+        FcvtLFmt => fpu("fcvt.l.{}", "f", 0b11000, None).rs2(2).regs(X, F),
+        FcvtLuFmt => fpu("fcvt.lu.{}", "f", 0b11000, None).rs2(3).regs(X, F),
+        FcvtFmtW => fpu("fcvt.{}.w", "f", 0b11010, None).regs(F, X),
+        FcvtFmtWu => fpu("fcvt.{}.wu", "f", 0b11010, None).rs2(1).regs(F, X),
+        FcvtFmtL => fpu("fcvt.{}.l", "f", 0b11010, None).rs2(2).regs(F, X),
+        FcvtFmtLu => fpu("fcvt.{}.lu", "f", 0b11010, None).rs2(3).regs(F, X),
+        FmvXFmt => fpu("fmv.x.{x}", "f", 0b11100, Some(0b000)).regs(X, F),
+        FmvFmtX => fpu("fmv.{x}.x", "f", 0b11110, Some(0b000)).regs(F, X),
+        FcvtSD => fpu("fcvt.s.d", "f", 0b01000, None).rs2(1).widths(S),
+        FcvtDS => fpu("fcvt.d.s", "f", 0b01000, None).widths(D),
+        Fround => fpu("fround.{}", "zfa", 0b01000, None).rs2(0b00100),
+    });
+
+    let (flags, isa_flags) = make_test_flags();
+    let emit_info = EmitInfo::new(flags, isa_flags);
+
+    for (alu_op, golden) in table {
+        for &width in golden.widths {
+            for (i, &(rd, rs, _, _)) in GOLDEN_OPERANDS.iter().enumerate() {
+                let (frm, funct3) = match golden.funct3 {
+                    Some(funct3) => (frm_for_funct3(funct3), funct3),
+                    None => GOLDEN_FRMS[i],
+                };
+                let inst = Inst::FpuRR {
+                    alu_op,
+                    width,
+                    frm,
+                    rd: Writable::from_reg(golden.rd.reg(rd)),
+                    rs: golden.rs.reg(rs),
+                };
+                let funct7 = golden.funct5 << 2 | golden_fmt(width);
+                let expected = r_type(OP_FP, rd, funct3, rs, golden.rs2, funct7);
+                let word = emit_word(&emit_info, &inst);
+                assert_eq!(
+                    word, expected,
+                    "{alu_op:?}.{width}: {word:#010x} != {expected:#010x}"
+                );
+
+                if i == 0 && golden.in_capstone(width) {
+                    let regs = [golden.rd.reg(rd), golden.rs.reg(rs)];
+                    capstone_check(word, &golden.name(width), &regs, None);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn riscv64_fpu_rrr_golden_encodings() {
+    use Class::*;
+    let table = golden_table!(FpuOPRRR {
+        Fadd => fpu("fadd.{}", "f", 0b00000, None),
+        Fsub => fpu("fsub.{}", "f", 0b00001, None),
+        Fmul => fpu("fmul.{}", "f", 0b00010, None),
+        Fdiv => fpu("fdiv.{}", "f", 0b00011, None),
+        Fsgnj => fpu("fsgnj.{}", "f", 0b00100, Some(0b000)),
+        Fsgnjn => fpu("fsgnjn.{}", "f", 0b00100, Some(0b001)),
+        Fsgnjx => fpu("fsgnjx.{}", "f", 0b00100, Some(0b010)),
+        Fmin => fpu("fmin.{}", "f", 0b00101, Some(0b000)),
+        Fmax => fpu("fmax.{}", "f", 0b00101, Some(0b001)),
+        Feq => fpu("feq.{}", "f", 0b10100, Some(0b010)).regs(X, F),
+        Flt => fpu("flt.{}", "f", 0b10100, Some(0b001)).regs(X, F),
+        Fle => fpu("fle.{}", "f", 0b10100, Some(0b000)).regs(X, F),
+        Fminm => fpu("fminm.{}", "zfa", 0b00101, Some(0b010)),
+        Fmaxm => fpu("fmaxm.{}", "zfa", 0b00101, Some(0b011)),
+    });
+
+    let (flags, isa_flags) = make_test_flags();
+    let emit_info = EmitInfo::new(flags, isa_flags);
+
+    for (alu_op, golden) in table {
+        for &width in golden.widths {
+            for (i, &(rd, rs1, rs2, _)) in GOLDEN_OPERANDS.iter().enumerate() {
+                let (frm, funct3) = match golden.funct3 {
+                    Some(funct3) => (frm_for_funct3(funct3), funct3),
+                    None => GOLDEN_FRMS[i],
+                };
+                let inst = Inst::FpuRRR {
+                    alu_op,
+                    width,
+                    frm,
+                    rd: Writable::from_reg(golden.rd.reg(rd)),
+                    rs1: golden.rs.reg(rs1),
+                    rs2: golden.rs.reg(rs2),
+                };
+                let funct7 = golden.funct5 << 2 | golden_fmt(width);
+                let expected = r_type(OP_FP, rd, funct3, rs1, rs2, funct7);
+                let word = emit_word(&emit_info, &inst);
+                assert_eq!(
+                    word, expected,
+                    "{alu_op:?}.{width}: {word:#010x} != {expected:#010x}"
+                );
+
+                if i == 0 && golden.in_capstone(width) {
+                    let regs = [golden.rd.reg(rd), golden.rs.reg(rs1), golden.rs.reg(rs2)];
+                    capstone_check(word, &golden.name(width), &regs, None);
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,8 @@
 //! Riscv64 ISA definitions: immediate constants.
 
 // Some variants are never constructed, but we still want them as options in the future.
-use super::Inst;
+use super::{AluOPRRI, AluOPRRR, Inst, Reg, Writable, zero_reg};
+use smallvec::SmallVec;
 use std::fmt::{Debug, Display, Formatter, Result};
 
 #[derive(Copy, Clone, Debug, Default)]
@@ -355,6 +356,254 @@ impl Inst {
     }
 }
 
+/// One step of a constant materialization sequence. The first step reads
+/// `zero`, every later step reads the result of the one before it.
+#[derive(Clone, Copy, Debug)]
+pub enum ImmOp {
+    Lui(Imm20),
+    Addi(Imm12),
+    Addiw(Imm12),
+    Slli(u8),
+    Srli(u8),
+    /// `slli.uw`, from Zba.
+    SlliUw(u8),
+    /// `add.uw rd, rs, zero`, i.e. `zext.w`, from Zba.
+    ZextW,
+    /// `sh{n}add rd, rs, rs`, multiplying by 3, 5 or 9. From Zba.
+    ShAdd(u8),
+    /// `bseti`, from Zbs.
+    Bseti(u8),
+    /// `bclri`, from Zbs.
+    Bclri(u8),
+}
+
+impl ImmOp {
+    pub(crate) fn gen_inst(self, rd: Writable<Reg>, rs: Reg) -> Inst {
+        let (alu_op, imm12) = match self {
+            ImmOp::Lui(imm) => return Inst::Lui { rd, imm },
+            ImmOp::ZextW => {
+                return Inst::AluRRR {
+                    alu_op: AluOPRRR::Adduw,
+                    rd,
+                    rs1: rs,
+                    rs2: zero_reg(),
+                };
+            }
+            ImmOp::ShAdd(n) => {
+                let alu_op = match n {
+                    1 => AluOPRRR::Sh1add,
+                    2 => AluOPRRR::Sh2add,
+                    3 => AluOPRRR::Sh3add,
+                    _ => unreachable!(),
+                };
+                return Inst::AluRRR {
+                    alu_op,
+                    rd,
+                    rs1: rs,
+                    rs2: rs,
+                };
+            }
+            ImmOp::Addi(imm) => (AluOPRRI::Addi, imm),
+            ImmOp::Addiw(imm) => (AluOPRRI::Addiw, imm),
+            ImmOp::Slli(n) => (AluOPRRI::Slli, Imm12::from_i16(n.into())),
+            ImmOp::Srli(n) => (AluOPRRI::Srli, Imm12::from_i16(n.into())),
+            ImmOp::SlliUw(n) => (AluOPRRI::SlliUw, Imm12::from_i16(n.into())),
+            ImmOp::Bseti(n) => (AluOPRRI::Bseti, Imm12::from_i16(n.into())),
+            ImmOp::Bclri(n) => (AluOPRRI::Bclri, Imm12::from_i16(n.into())),
+        };
+        Inst::AluRRImm12 {
+            alu_op,
+            rd,
+            rs,
+            imm12,
+        }
+    }
+}
+
+/// A sequence of instructions which materializes a 64-bit constant in a
+/// register without a load, following the approach of LLVM's `RISCVMatInt`.
+#[derive(Clone, Debug)]
+pub struct ImmSeq(SmallVec<[ImmOp; 8]>);
+
+impl ImmSeq {
+    /// Finds a short sequence materializing `value`, using the Zba and Zbs
+    /// instructions if they're available.
+    pub(crate) fn generate(value: u64, has_zba: bool, has_zbs: bool) -> ImmSeq {
+        let val = value as i64;
+        let mut seq = ImmSeq(SmallVec::new());
+        seq.generate_base(val, has_zba);
+
+        // A single bit is a single `bseti`.
+        if has_zbs && value.is_power_of_two() && i32::try_from(val).is_err() {
+            return ImmSeq::from_ops(&[ImmOp::Bseti(value.trailing_zeros() as u8)]);
+        }
+
+        // If the low 12 bits are nonzero the sequence above ends with an
+        // `addi`. Trailing zeros can instead be chopped off and restored with
+        // a final shift.
+        if (val & 0xfff) != 0 && (val & 1) == 0 && seq.len() > 2 {
+            let tz = val.trailing_zeros() as u8;
+            let mut tmp = ImmSeq::base(val >> tz, has_zba);
+            tmp.push(ImmOp::Slli(tz));
+            seq.replace_if_shorter(tmp);
+
+            let shifted = value >> tz;
+            if has_zba && u32::try_from(shifted).is_ok() && i32::try_from(shifted as i64).is_err() {
+                let mut tmp = ImmSeq::base((shifted | 0xffff_ffff_0000_0000) as i64, has_zba);
+                tmp.push(ImmOp::SlliUw(tz));
+                seq.replace_if_shorter(tmp);
+            }
+        }
+
+        // Positive values can also be built with their leading zeros shifted
+        // out, shifting them back in with a final `srli`. The vacated low bits
+        // are free to be either zeros or ones.
+        if val > 0 && seq.len() > 2 {
+            let lz = value.leading_zeros() as u8;
+            let shifted = value << lz;
+            for low_bits in [(1u64 << lz) - 1, 0] {
+                let mut tmp = ImmSeq::base((shifted | low_bits) as i64, has_zba);
+                tmp.push(ImmOp::Srli(lz));
+                seq.replace_if_shorter(tmp);
+            }
+
+            if lz == 32 && has_zba {
+                let mut tmp = ImmSeq::base(i64::from(value as i32), has_zba);
+                tmp.push(ImmOp::ZextW);
+                seq.replace_if_shorter(tmp);
+            }
+        }
+
+        // Multiples of 3, 5 and 9 are a smaller constant and a `sh{n}add`.
+        if has_zba && seq.len() > 2 {
+            for (div, n) in [(3, 1), (5, 2), (9, 3)] {
+                if val % div == 0 {
+                    let mut tmp = ImmSeq::base(val / div, has_zba);
+                    tmp.push(ImmOp::ShAdd(n));
+                    seq.replace_if_shorter(tmp);
+                }
+            }
+        }
+
+        // Set or clear the bits above bit 30 individually on top of a 32-bit
+        // constant.
+        if has_zbs && seq.len() > 2 {
+            const HIGH: u64 = !0x7fff_ffff;
+            for (low, bits, op) in [
+                (value & !HIGH, value & HIGH, ImmOp::Bseti as fn(u8) -> ImmOp),
+                (value | HIGH, !value & HIGH, ImmOp::Bclri),
+            ] {
+                if bits.count_ones() as usize >= seq.len() {
+                    continue;
+                }
+                let mut tmp = if low == 0 {
+                    ImmSeq(SmallVec::new())
+                } else {
+                    ImmSeq::base(low as i64, has_zba)
+                };
+                let mut bits = bits;
+                while bits != 0 {
+                    tmp.push(op(bits.trailing_zeros() as u8));
+                    bits &= bits - 1;
+                }
+                seq.replace_if_shorter(tmp);
+            }
+        }
+
+        seq
+    }
+
+    fn from_ops(ops: &[ImmOp]) -> ImmSeq {
+        ImmSeq(ops.into())
+    }
+
+    fn base(val: i64, has_zba: bool) -> ImmSeq {
+        let mut seq = ImmSeq(SmallVec::new());
+        seq.generate_base(val, has_zba);
+        seq
+    }
+
+    /// Appends the recursive `lui`/`addi`/`slli` decomposition of `val`.
+    fn generate_base(&mut self, val: i64, has_zba: bool) {
+        if i32::try_from(val).is_ok() {
+            let lo12 = (val << 52) >> 52;
+            let hi20 = ((val + 0x800) << 32) >> 44;
+            if hi20 != 0 {
+                self.push(ImmOp::Lui(Imm20::from_i32(hi20 as i32)));
+            }
+            if lo12 != 0 || hi20 == 0 {
+                let imm12 = Imm12::from_i16(lo12 as i16);
+                // `lui` sign-extends, so adding the low bits needs to wrap at
+                // 32 bits when that crosses into the sign bit.
+                if hi20 != 0 && val > Inst::imm_max() {
+                    self.push(ImmOp::Addiw(imm12));
+                } else {
+                    self.push(ImmOp::Addi(imm12));
+                }
+            }
+            return;
+        }
+
+        // Materialize the upper 52 bits, with their trailing zeros stripped,
+        // then shift them into place and add the low 12 bits.
+        let lo12 = (val << 52) >> 52;
+        let hi52 = ((val as u64).wrapping_add(0x800) >> 12) as i64;
+        let mut shift = hi52.trailing_zeros() + 12;
+        let mut hi52 = ((((hi52 as u64) >> (shift - 12)) << shift) as i64) >> shift;
+
+        // If the remaining bits don't fit in 12 bits, shifting 12 bits less
+        // may allow a single `lui` to produce them.
+        let mut unsigned = false;
+        if shift > 12 && Imm12::maybe_from_i64(hi52).is_none() {
+            let widened = (hi52 as u64) << 12;
+            if i32::try_from(widened as i64).is_ok() {
+                shift -= 12;
+                hi52 = widened as i64;
+            } else if has_zba && u32::try_from(widened).is_ok() {
+                shift -= 12;
+                hi52 = (widened | 0xffff_ffff_0000_0000) as i64;
+                unsigned = true;
+            }
+        }
+
+        // An unsigned 32-bit value can be built sign-extended and then
+        // zero-extended by `slli.uw`.
+        if has_zba && u32::try_from(hi52).is_ok() && i32::try_from(hi52).is_err() {
+            hi52 = (hi52 as u64 | 0xffff_ffff_0000_0000) as i64;
+            unsigned = true;
+        }
+
+        self.generate_base(hi52, has_zba);
+        let shift = shift as u8;
+        self.push(if unsigned {
+            ImmOp::SlliUw(shift)
+        } else {
+            ImmOp::Slli(shift)
+        });
+        if lo12 != 0 {
+            self.push(ImmOp::Addi(Imm12::from_i16(lo12 as i16)));
+        }
+    }
+
+    fn push(&mut self, op: ImmOp) {
+        self.0.push(op);
+    }
+
+    fn replace_if_shorter(&mut self, other: ImmSeq) {
+        if other.len() < self.len() {
+            *self = other;
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub(crate) fn ops(&self) -> &[ImmOp] {
+        &self.0
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -370,4 +619,95 @@ mod test {
         assert!(Inst::imm_max() == (i32::MAX - 2048) as i64);
         assert!(Inst::imm_min() == i32::MIN as i64 - 2048);
     }
+
+    fn eval(seq: &ImmSeq) -> u64 {
+        seq.ops().iter().fold(0, |r: u64, op| match *op {
+            ImmOp::Lui(imm) => (i64::from(imm.as_i32()) << 12) as u64,
+            ImmOp::Addi(imm) => r.wrapping_add(imm.as_i16() as u64),
+            ImmOp::Addiw(imm) => i64::from((r as i32).wrapping_add(imm.as_i16().into())) as u64,
+            ImmOp::Slli(n) => r << n,
+            ImmOp::Srli(n) => r >> n,
+            ImmOp::SlliUw(n) => (r & 0xffff_ffff) << n,
+            ImmOp::ZextW => r & 0xffff_ffff,
+            ImmOp::ShAdd(n) => (r << n).wrapping_add(r),
+            ImmOp::Bseti(n) => r | (1 << n),
+            ImmOp::Bclri(n) => r & !(1 << n),
+        })
+    }
+
+    #[test]
+    fn imm_seq_lengths() {
+        // Each constant with the longest sequence allowed without extensions
+        // and with both Zba and Zbs.
+        let corpus: &[(u64, usize, usize)] = &[
+            (0, 1, 1),
+            (1, 1, 1),
+            (u64::MAX, 1, 1),
+            (0x7ff, 1, 1),
+            (0x800, 2, 2),
+            (0x1000, 1, 1),
+            (0x7fff_f7ff, 2, 2),
+            (0x7fff_f800, 2, 2),
+            (0x7fff_ffff, 2, 2),
+            (0x8000_0000, 2, 1),
+            (0xffff_ffff, 2, 2),
+            (0x1_0000_0000, 2, 1),
+            (0x1_0000_0001, 3, 2),
+            (0x0000_7fff_ffff_f800, 2, 2),
+            (0x0000_ffff_ffff_ffff, 2, 2),
+            (0xffff_ffff_0000_0000, 2, 2),
+            (0x8000_0000_0000_0000, 2, 1),
+            (0x7fff_ffff_ffff_ffff, 2, 2),
+            (0x0000_4000_0000_0000, 2, 1),
+            (0xffff_bfff_ffff_ffff, 3, 2),
+            (0x1234_5678_0000_0000, 3, 3),
+            (0x0000_0012_3456_7000, 3, 3),
+            (0x0000_0000_dead_beef, 3, 3),
+            (0x8000_0000_0000_0001, 3, 2),
+            (0x0000_0003_0000_0003, 3, 3),
+            (0x0101_0101_0101_0101, 6, 6),
+            (0x1234_5678_9abc_def0, 8, 7),
+        ];
+        for &(value, base_len, ext_len) in corpus {
+            let base = ImmSeq::generate(value, false, false);
+            assert_eq!(eval(&base), value, "{value:#x}: {base:?}");
+            assert!(base.len() <= base_len, "{value:#x}: {base:?}");
+            let ext = ImmSeq::generate(value, true, true);
+            assert_eq!(eval(&ext), value, "{value:#x}: {ext:?}");
+            assert!(ext.len() <= ext_len, "{value:#x}: {ext:?}");
+        }
+    }
+
+    #[test]
+    fn imm_seq_values() {
+        let mut values = alloc::vec::Vec::new();
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        for _ in 0..10_000 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            values.push(state);
+            values.push(state >> (state % 64));
+            values.push(state << (state % 64));
+            values.push((state as i64 >> (state % 64)) as u64);
+        }
+        for bit in 0..64 {
+            values.push(1 << bit);
+            values.push(!(1 << bit));
+            values.push((1 << bit) - 1);
+            values.push(0xdead_beef << bit.min(32));
+            values.push((-0x7ff_i64 as u64) << bit);
+        }
+        for value in -4096i64..4096 {
+            values.push(value as u64);
+            values.push(0x8000_0000u64.wrapping_add(value as u64));
+        }
+        for value in values {
+            for (zba, zbs) in [(false, false), (true, false), (false, true), (true, true)] {
+                let seq = ImmSeq::generate(value, zba, zbs);
+                assert_eq!(eval(&seq), value, "{value:#x}: {seq:?}");
+                assert!(seq.len() <= 8, "{value:#x}: {seq:?}");
+            }
+        }
+    }
 }
@@ -176,15 +176,27 @@ impl Inst {
         })
     }
 
+    /// Materializes `value` with a sequence of base ISA instructions, falling
+    /// back to an inline constant when that sequence would be larger than one.
     pub fn load_constant_u64(rd: Writable<Reg>, value: u64) -> SmallInstVec<Inst> {
-        let insts = Inst::load_const_imm(rd, value);
-        insts.unwrap_or_else(|| {
-            smallvec![Inst::LoadInlineConst {
+        let seq = ImmSeq::generate(value, false, false);
+        // `LoadInlineConst` is a load, a jump and 8 bytes of data.
+        if seq.len() > 5 {
+            return smallvec![Inst::LoadInlineConst {
                 rd,
                 ty: I64,
                 imm: value
-            }]
-        })
+            }];
+        }
+        let mut rs = zero_reg();
+        seq.ops()
+            .iter()
+            .map(|op| {
+                let inst = op.gen_inst(rd, rs);
+                rs = rd.to_reg();
+                inst
+            })
+            .collect()
     }
 
     pub(crate) fn construct_auipc_and_jalr(
@@ -390,16 +402,26 @@ fn riscv64_get_operands(inst: &mut Inst, collector: &mut impl OperandVisitor) {
             collector.reg_use(rs1);
             collector.reg_use(rs2);
         }
+        Inst::CondBrOr { kind1, kind2, .. } => {
+            collector.reg_use(&mut kind1.rs1);
+            collector.reg_use(&mut kind1.rs2);
+            collector.reg_use(&mut kind2.rs1);
+            collector.reg_use(&mut kind2.rs2);
+        }
         Inst::LoadExtName { rd, .. } => {
             collector.reg_def(rd);
         }
         Inst::ElfTlsGetAddr { rd, .. } => {
             // x10 is a0 which is both the first argument and the first return value.
             collector.reg_fixed_def(rd, a0());
-            let mut clobbers =
-                Riscv64MachineDeps::get_regs_clobbered_by_call(CallConv::SystemV, false);
-            clobbers.remove(px_reg(10));
-            collector.reg_clobbers(clobbers);
+            collector.reg_clobbers(Riscv64MachineDeps::libcall_clobbers(
+                CallConv::SystemV,
+                [px_reg(10)],
+            ));
+        }
+        Inst::ElfTlsInitialExec { rd, .. } | Inst::ElfTlsLocalExec { rd, .. } => {
+            // Only `tp` is read, and it is never allocatable.
+            collector.reg_def(rd);
         }
         Inst::LoadAddr { rd, mem } => {
             mem.get_operands(collector);
@@ -439,6 +461,14 @@ fn riscv64_get_operands(inst: &mut Inst, collector: &mut impl OperandVisitor) {
             collector.reg_use(src);
             collector.reg_def(rd);
         }
+        Inst::Amocas {
+            rd, e, addr, src, ..
+        } => {
+            collector.reg_use(addr);
+            collector.reg_use(src);
+            collector.reg_use(e);
+            collector.reg_reuse_def(rd, 2); // `rd` == `e`.
+        }
         Inst::Select {
             dst,
             condition: IntegerCompare { rs1, rs2, .. },
@@ -488,6 +518,11 @@ fn riscv64_get_operands(inst: &mut Inst, collector: &mut impl OperandVisitor) {
             v,
             ..
         } => {
+            // The whole LR/SC loop is a single instruction so nothing can be
+            // placed between the `lr` and the `sc`. Its defs are written
+            // while the uses are still needed by later iterations, so they
+            // must not share a register with them. Sub-word operations merge
+            // the containing word in `t0` before storing it back.
             collector.reg_use(offset);
             collector.reg_use(e);
             collector.reg_use(addr);
@@ -511,13 +546,21 @@ fn riscv64_get_operands(inst: &mut Inst, collector: &mut impl OperandVisitor) {
             p,
             x,
             t0,
+            word,
+            ty,
             ..
         } => {
+            // Same constraints as `AtomicCas` above. `t0` holds the new lane
+            // value here, so sub-word operations need a separate register for
+            // the containing word.
             collector.reg_use(offset);
             collector.reg_use(p);
             collector.reg_use(x);
             collector.reg_early_def(t0);
             collector.reg_early_def(dst);
+            if ty.bits() < 32 {
+                collector.reg_early_def(word);
+            }
         }
         Inst::TrapIf { rs1, rs2, .. } => {
             collector.reg_use(rs1);
@@ -527,22 +570,6 @@ fn riscv64_get_operands(inst: &mut Inst, collector: &mut impl OperandVisitor) {
         Inst::DummyUse { reg } => {
             collector.reg_use(reg);
         }
-        Inst::Popcnt {
-            sum, step, rs, tmp, ..
-        } => {
-            collector.reg_use(rs);
-            collector.reg_early_def(tmp);
-            collector.reg_early_def(step);
-            collector.reg_early_def(sum);
-        }
-        Inst::Cltz {
-            sum, step, tmp, rs, ..
-        } => {
-            collector.reg_use(rs);
-            collector.reg_early_def(tmp);
-            collector.reg_early_def(step);
-            collector.reg_early_def(sum);
-        }
         Inst::Brev8 {
             rs,
             rd,
@@ -730,7 +757,11 @@ impl MachInst for Inst {
 
     fn is_move(&self) -> Option<(Writable<Reg>, Reg)> {
         match self {
-            Inst::Mov { rd, rm, .. } => Some((*rd, *rm)),
+            // Narrower float moves use a `fsgnj` of that width, which
+            // NaN-boxes its result instead of copying the whole register.
+            Inst::Mov { rd, rm, ty } if rm.class() != RegClass::Float || *ty == F64 => {
+                Some((*rd, *rm))
+            }
             _ => None,
         }
     }
@@ -760,6 +791,7 @@ impl MachInst for Inst {
         match self {
             &Inst::Jal { .. } => MachTerminator::Branch,
             &Inst::CondBr { .. } => MachTerminator::Branch,
+            &Inst::CondBrOr { .. } => MachTerminator::Branch,
             &Inst::Jalr { .. } => MachTerminator::Branch,
             &Inst::Rets { .. } => MachTerminator::Ret,
             &Inst::BrTable { .. } => MachTerminator::Branch,
@@ -774,6 +806,33 @@ impl MachInst for Inst {
         panic!("TODO FILL ME OUT")
     }
 
+    fn is_frame_free(&self) -> bool {
+        let is_frame_reg =
+            |base: Option<Reg>| base.is_some_and(|base| base == stack_reg() || base == fp_reg());
+        match self {
+            // Calls overwrite `ra`, and the rest read or move `sp` or `fp`.
+            Inst::Call { .. }
+            | Inst::CallInd { .. }
+            | Inst::ReturnCall { .. }
+            | Inst::ReturnCallInd { .. }
+            | Inst::ElfTlsGetAddr { .. }
+            | Inst::Jalr { .. }
+            | Inst::MovFromPReg { .. }
+            | Inst::StackProbeLoop { .. }
+            | Inst::Unwind { .. } => false,
+            // The trap is deferred to an island after the body, where the
+            // unwind info describes the frame that the body sets up.
+            Inst::TrapIf { .. } => false,
+            Inst::Load { from: mem, .. }
+            | Inst::Store { to: mem, .. }
+            | Inst::LoadAddr { mem, .. } => !is_frame_reg(mem.get_base_register()),
+            Inst::VecLoad { from: mem, .. } | Inst::VecStore { to: mem, .. } => {
+                !is_frame_reg(mem.get_base_register())
+            }
+            _ => true,
+        }
+    }
+
     fn gen_move(to_reg: Writable<Reg>, from_reg: Reg, ty: Type) -> Inst {
         let x = Inst::Mov {
             rd: to_reg,
@@ -966,7 +1025,7 @@ impl Inst {
                     "inline_stack_probe##guard_size={guard_size} probe_count={probe_count} tmp={tmp}"
                 )
             }
-            &Inst::AtomicStore { src, ty, p } => {
+            &Inst::AtomicStore { src, ty, p, .. } => {
                 let src = format_reg(src);
                 let p = format_reg(p);
                 format!("atomic_store.{ty} {src},({p})")
@@ -976,7 +1035,7 @@ impl Inst {
                 format!("dummy_use {reg}")
             }
 
-            &Inst::AtomicLoad { rd, ty, p } => {
+            &Inst::AtomicLoad { rd, ty, p, .. } => {
                 let p = format_reg(p);
                 let rd = format_reg(rd.to_reg());
                 format!("atomic_load.{ty} {rd},({p})")
@@ -989,13 +1048,22 @@ impl Inst {
                 p,
                 x,
                 t0,
+                word,
+                ..
             } => {
                 let offset = format_reg(offset);
                 let p = format_reg(p);
                 let x = format_reg(x);
                 let t0 = format_reg(t0.to_reg());
                 let dst = format_reg(dst.to_reg());
-                format!("atomic_rmw.{ty} {op} {dst},{x},({p})##t0={t0} offset={offset}")
+                if ty.bits() < 32 {
+                    let word = format_reg(word.to_reg());
+                    format!(
+                        "atomic_rmw.{ty} {op} {dst},{x},({p})##t0={t0} word={word} offset={offset}"
+                    )
+                } else {
+                    format!("atomic_rmw.{ty} {op} {dst},{x},({p})##t0={t0} offset={offset}")
+                }
             }
 
             &Inst::RawData { ref data } => match data.len() {
@@ -1035,41 +1103,6 @@ impl Inst {
                 let rd = format_reg(rd.to_reg());
                 format!("brev8 {rd},{rs}##tmp={tmp} tmp2={tmp2} step={step} ty={ty}")
             }
-            &Inst::Popcnt {
-                sum,
-                step,
-                rs,
-                tmp,
-                ty,
-            } => {
-                let rs = format_reg(rs);
-                let tmp = format_reg(tmp.to_reg());
-                let step = format_reg(step.to_reg());
-                let sum = format_reg(sum.to_reg());
-                format!("popcnt {sum},{rs}##ty={ty} tmp={tmp} step={step}")
-            }
-            &Inst::Cltz {
-                sum,
-                step,
-                rs,
-                tmp,
-                ty,
-                leading,
-            } => {
-                let rs = format_reg(rs);
-                let tmp = format_reg(tmp.to_reg());
-                let step = format_reg(step.to_reg());
-                let sum = format_reg(sum.to_reg());
-                format!(
-                    "{} {},{}##ty={} tmp={} step={}",
-                    if leading { "clz" } else { "ctz" },
-                    sum,
-                    rs,
-                    ty,
-                    tmp,
-                    step
-                )
-            }
             &Inst::AtomicCas {
                 offset,
                 t0,
@@ -1078,6 +1111,7 @@ impl Inst {
                 addr,
                 v,
                 ty,
+                ..
             } => {
                 let offset = format_reg(offset);
                 let e = format_reg(e);
@@ -1394,6 +1428,24 @@ impl Inst {
                     x
                 }
             }
+            &MInst::CondBrOr {
+                taken,
+                not_taken,
+                kind1,
+                kind2,
+            } => {
+                format!(
+                    "{} {},{} || {} {},{},taken({}),not_taken({})",
+                    kind1.op_name(),
+                    format_reg(kind1.rs1),
+                    format_reg(kind1.rs2),
+                    kind2.op_name(),
+                    format_reg(kind2.rs1),
+                    format_reg(kind2.rs2),
+                    taken,
+                    not_taken
+                )
+            }
             &MInst::Atomic {
                 op,
                 rd,
@@ -1411,10 +1463,25 @@ impl Inst {
                     format!("{op_name} {rd},{src},({addr})")
                 }
             }
+            &MInst::Amocas {
+                op,
+                rd,
+                addr,
+                src,
+                amo,
+                ..
+            } => {
+                let op_name = op.op_name(amo);
+                let addr = format_reg(addr);
+                let src = format_reg(src);
+                let rd = format_reg(rd.to_reg());
+                format!("{op_name} {rd},{src},({addr})")
+            }
             &MInst::LoadExtName {
                 rd,
                 ref name,
                 offset,
+                ..
             } => {
                 let rd = format_reg(rd.to_reg());
                 format!("load_sym {},{}{:+}", rd, name.display(None), offset)
@@ -1423,6 +1490,14 @@ impl Inst {
                 let rd = format_reg(rd.to_reg());
                 format!("elf_tls_get_addr {rd},{}", name.display(None))
             }
+            &Inst::ElfTlsInitialExec { rd, ref name } => {
+                let rd = format_reg(rd.to_reg());
+                format!("elf_tls_initial_exec {rd},{}", name.display(None))
+            }
+            &Inst::ElfTlsLocalExec { rd, ref name } => {
+                let rd = format_reg(rd.to_reg());
+                format!("elf_tls_local_exec {rd},{}", name.display(None))
+            }
             &MInst::LoadAddr { ref rd, ref mem } => {
                 let rs = mem.to_string();
                 let rd = format_reg(rd.to_reg());
@@ -1687,6 +1762,11 @@ pub enum LabelUse {
 
     /// 11-bit PC-relative jump offset. Equivalent to the `RVC_JUMP` relocation
     RVCJump,
+
+    /// 32-bit PC-relative offset stored as a data word. The offset is added to
+    /// the value already present in the word, which allows jump table entries
+    /// to be relative to the start of the table instead of the entry itself.
+    PCRel32Data,
 }
 
 impl MachInstLabelUse for LabelUse {
@@ -1698,9 +1778,10 @@ impl MachInstLabelUse for LabelUse {
     fn max_pos_range(self) -> CodeOffset {
         match self {
             LabelUse::Jal20 => ((1 << 19) - 1) * 2,
-            LabelUse::PCRelLo12I | LabelUse::PCRelHi20 | LabelUse::PCRel32 => {
-                Inst::imm_max() as CodeOffset
-            }
+            LabelUse::PCRelLo12I
+            | LabelUse::PCRelHi20
+            | LabelUse::PCRel32
+            | LabelUse::PCRel32Data => Inst::imm_max() as CodeOffset,
             LabelUse::B12 => ((1 << 11) - 1) * 2,
             LabelUse::RVCJump => ((1 << 10) - 1) * 2,
         }
@@ -1709,7 +1790,7 @@ impl MachInstLabelUse for LabelUse {
     /// Maximum PC-relative range (negative).
     fn max_neg_range(self) -> CodeOffset {
         match self {
-            LabelUse::PCRel32 => Inst::imm_min().abs() as CodeOffset,
+            LabelUse::PCRel32 | LabelUse::PCRel32Data => Inst::imm_min().abs() as CodeOffset,
             _ => self.max_pos_range() + 2,
         }
     }
@@ -1718,7 +1799,11 @@ impl MachInstLabelUse for LabelUse {
     fn patch_size(self) -> CodeOffset {
         match self {
             LabelUse::RVCJump => 2,
-            LabelUse::Jal20 | LabelUse::B12 | LabelUse::PCRelHi20 | LabelUse::PCRelLo12I => 4,
+            LabelUse::Jal20
+            | LabelUse::B12
+            | LabelUse::PCRelHi20
+            | LabelUse::PCRelLo12I
+            | LabelUse::PCRel32Data => 4,
             LabelUse::PCRel32 => 8,
         }
     }
@@ -1869,6 +1954,10 @@ impl LabelUse {
                 let insn = (insn & 0xFFFFF) | (lo12 << 20);
                 buffer[0..4].clone_from_slice(&u32::to_le_bytes(insn));
             }
+            LabelUse::PCRel32Data => {
+                let value = insn.wrapping_add(offset as u32);
+                buffer[0..4].clone_from_slice(&u32::to_le_bytes(value));
+            }
             LabelUse::RVCJump => {
                 debug_assert!(offset & 1 == 0);
 
@@ -1888,6 +1977,98 @@ impl LabelUse {
 #[cfg(test)]
 mod test {
     use super::*;
+    use regalloc2::{OperandConstraint, OperandKind, OperandPos, VReg};
+
+    #[test]
+    fn is_move_only_for_full_register_copies() {
+        let mov = |rd: Reg, rm: Reg, ty| Inst::Mov {
+            rd: Writable::from_reg(rd),
+            rm,
+            ty,
+        };
+        for (inst, full) in [
+            (mov(x_reg(10), x_reg(11), I64), true),
+            (mov(x_reg(10), x_reg(11), I8), true),
+            (mov(f_reg(10), f_reg(11), F64), true),
+            (mov(f_reg(10), f_reg(11), F32), false),
+            (mov(f_reg(10), f_reg(11), F16), false),
+            (mov(pv_reg(10).into(), pv_reg(11).into(), I8X16), true),
+        ] {
+            assert_eq!(inst.is_move().is_some(), full, "{inst:?}");
+        }
+        assert!(
+            Inst::gen_move(Writable::from_reg(f_reg(1)), f_reg(2), F64)
+                .is_move()
+                .is_some()
+        );
+        assert!(
+            Inst::load_imm12(Writable::from_reg(x_reg(10)), Imm12::ZERO)
+                .is_move()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn multi_temp_pseudos_use_early_defs() {
+        // These expand to sequences that write their temporaries and results
+        // before they are done reading their inputs, so no def may share a
+        // register with a use.
+        let v = |n: usize| Reg::from(VReg::new(200 + n, RegClass::Int));
+        let w = |n| Writable::from_reg(v(n));
+        let label = MachLabel::from_block(BlockIndex::new(0));
+        let mut insts = vec![
+            Inst::BrTable {
+                index: v(10),
+                tmp1: w(11),
+                tmp2: w(12),
+                targets: vec![label; 4],
+            },
+            Inst::AtomicCas {
+                offset: v(10),
+                t0: w(11),
+                dst: w(12),
+                e: v(13),
+                addr: v(14),
+                v: v(15),
+                ty: I8,
+                amo: AMO::SeqCst,
+            },
+            Inst::AtomicRmwLoop {
+                offset: v(10),
+                op: crate::ir::AtomicRmwOp::Add,
+                dst: w(11),
+                ty: I16,
+                p: v(12),
+                x: v(13),
+                t0: w(14),
+                word: w(15),
+                amo: AMO::SeqCst,
+            },
+            Inst::Brev8 {
+                rs: v(10),
+                ty: I64,
+                step: w(11),
+                tmp: w(12),
+                tmp2: w(13),
+                rd: w(14),
+            },
+        ];
+        for inst in &mut insts {
+            let mut defs = 0;
+            let name = format!("{inst:?}");
+            inst.get_operands(&mut |_: &mut Reg,
+                                    _: OperandConstraint,
+                                    kind: OperandKind,
+                                    pos: OperandPos| {
+                if kind == OperandKind::Def {
+                    defs += 1;
+                    assert_eq!(pos, OperandPos::Early, "{name}");
+                }
+            });
+            assert!(defs > 0, "{name}");
+        }
+    }
+
     #[test]
     fn label_use_max_range() {
         assert!(LabelUse::B12.max_neg_range() == LabelUse::B12.max_pos_range() + 2);
@@ -1896,4 +2077,95 @@ mod test {
         assert!(LabelUse::PCRel32.max_neg_range() == (Inst::imm_min().abs() as CodeOffset));
         assert!(LabelUse::B12.max_pos_range() == ((1 << 11) - 1) * 2);
     }
+
+    /// Patches `insns` with `label_use` for a label `offset` bytes away.
+    fn patch(label_use: LabelUse, insns: &[u32], offset: i64) -> Vec<u32> {
+        let mut buffer = insns
+            .iter()
+            .flat_map(|insn| insn.to_le_bytes())
+            .collect::<Vec<_>>();
+        // Far enough into the buffer that all labels in range are reachable.
+        let use_offset = 0x8000_0800;
+        let label_offset = use_offset + offset;
+        label_use.patch(
+            &mut buffer[..label_use.patch_size() as usize],
+            use_offset as CodeOffset,
+            label_offset as CodeOffset,
+        );
+        buffer
+            .chunks(4)
+            .map(|c| u32::from_le_bytes(c.try_into().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn label_use_patch_at_max_range() {
+        // beq zero, zero, ...
+        let beq = 0x00000063;
+        assert_eq!(patch(LabelUse::B12, &[beq], 4094), [0x7e000fe3]);
+        assert_eq!(patch(LabelUse::B12, &[beq], -4096), [0x80000063]);
+
+        // jal zero, ...
+        let jal = 0x0000006f;
+        assert_eq!(patch(LabelUse::Jal20, &[jal], (1 << 20) - 2), [0x7ffff06f]);
+        assert_eq!(patch(LabelUse::Jal20, &[jal], -(1 << 20)), [0x8000006f]);
+
+        // c.j ...; padded with c.nop so that the buffer holds whole words.
+        let cj = 0x0001_a001;
+        assert_eq!(patch(LabelUse::RVCJump, &[cj], 2046), [0x0001_affd]);
+        assert_eq!(patch(LabelUse::RVCJump, &[cj], -2048), [0x0001_b001]);
+
+        // auipc t6, ...; jalr zero, ...(t6)
+        let auipc_jalr = [0x00000f97, 0x000f8067];
+        // Labels are 2-byte aligned, and the maximum is odd.
+        let max = Inst::imm_max() - 1;
+        let min = Inst::imm_min();
+        assert_eq!(
+            patch(LabelUse::PCRel32, &auipc_jalr, max),
+            [0x7fffff97, 0x7fef8067]
+        );
+        assert_eq!(
+            patch(LabelUse::PCRel32, &auipc_jalr, min),
+            [0x80000f97, 0x800f8067]
+        );
+        // The low 12 bits are sign extended, so 2048 rounds the high 20 up.
+        assert_eq!(
+            patch(LabelUse::PCRel32, &auipc_jalr, 2046),
+            [0x00000f97, 0x7fef8067]
+        );
+        assert_eq!(
+            patch(LabelUse::PCRel32, &auipc_jalr, 2048),
+            [0x00001f97, 0x800f8067]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "must not exceed max range")]
+    fn label_use_patch_out_of_range() {
+        patch(LabelUse::B12, &[0x00000063], 4096);
+    }
+
+    #[test]
+    fn label_use_veneer() {
+        for label_use in [LabelUse::B12, LabelUse::Jal20, LabelUse::RVCJump] {
+            assert!(label_use.supports_veneer());
+            let mut buffer = [0; 8];
+            let size = label_use.veneer_size() as usize;
+            let (use_offset, veneer_use) = label_use.generate_veneer(&mut buffer[..size], 0x100);
+            assert_eq!(use_offset, 0x100);
+            assert_eq!(veneer_use, LabelUse::PCRel32);
+
+            // The veneer is `auipc t6, 0; jalr zero, 0(t6)`, which can then be
+            // patched to reach its label wherever it is.
+            let insns = buffer
+                .chunks(4)
+                .map(|c| u32::from_le_bytes(c.try_into().unwrap()))
+                .collect::<Vec<_>>();
+            assert_eq!(insns, [0x00000f97, 0x000f8067]);
+            assert_eq!(
+                patch(veneer_use, &insns, 0x1234_4f00),
+                [0x12345f97, 0xf00f8067]
+            );
+        }
+    }
 }
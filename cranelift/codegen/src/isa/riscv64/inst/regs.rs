@@ -91,6 +91,15 @@ pub fn writable_link_reg() -> Writable<Reg> {
     Writable::from_reg(link_reg())
 }
 
+/// Get a reference to the thread pointer (x4).
+///
+/// This register is never allocatable, so it always holds the address of the
+/// current thread's TLS block.
+#[inline]
+pub fn tp_reg() -> Reg {
+    x_reg(4)
+}
+
 /// Get a reference to the frame pointer (x8).
 #[inline]
 pub fn fp_reg() -> Reg {
@@ -116,18 +125,6 @@ pub fn writable_spilltmp_reg() -> Writable<Reg> {
     Writable::from_reg(spilltmp_reg())
 }
 
-///spilltmp2
-#[inline]
-pub fn spilltmp_reg2() -> Reg {
-    x_reg(30)
-}
-
-/// Get a writable reference to the spilltmp2 reg.
-#[inline]
-pub fn writable_spilltmp_reg2() -> Writable<Reg> {
-    Writable::from_reg(spilltmp_reg2())
-}
-
 #[inline]
 pub const fn x_reg(enc: usize) -> Reg {
     let p_reg = PReg::new(enc, RegClass::Int);
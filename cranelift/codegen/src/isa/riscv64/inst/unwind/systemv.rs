@@ -63,11 +63,14 @@ mod tests {
 
     use crate::Context;
     use crate::ir::{
-        AbiParam, Function, InstBuilder, Signature, StackSlotData, StackSlotKind, UserFuncName,
-        types,
+        AbiParam, ExtFuncData, ExternalName, Function, InstBuilder, MemFlags, Signature,
+        StackSlotData, StackSlotKind, TrapCode, UserExternalNameRef, UserFuncName, types,
     };
     use crate::isa::{CallConv, lookup};
-    use crate::settings::{Flags, builder};
+    use crate::settings::{Configurable, Flags, builder};
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use cranelift_entity::EntityRef as _;
     use gimli::write::Address;
     use target_lexicon::triple;
 
@@ -103,6 +106,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tail_stack_args_func() {
+        let isa = lookup(triple!("riscv64"))
+            .expect("expect riscv64 ISA")
+            .finish(Flags::new(builder()))
+            .expect("Creating compiler backend");
+
+        let mut context = Context::for_function(create_stack_args_function(CallConv::Tail));
+
+        let code = context
+            .compile(&*isa, &mut Default::default())
+            .expect("expected compilation");
+
+        let fde = match code
+            .create_unwind_info(isa.as_ref())
+            .expect("can create unwind info")
+        {
+            Some(crate::isa::unwind::UnwindInfo::SystemV(info)) => {
+                info.to_fde(Address::Constant(1234))
+            }
+            _ => panic!("expected unwind information"),
+        };
+
+        // The epilogue pops the frame and the stack arguments with a single
+        // `addi`, which must leave the prologue's CFA rules where they were.
+        assert_eq!(
+            format!("{fde:?}"),
+            "FrameDescriptionEntry { address: Constant(1234), length: 36, lsda: None, instructions: [(12, CfaOffset(16)), (12, Offset(Register(8), -16)), (12, Offset(Register(1), -8)), (16, CfaRegister(Register(8)))] }"
+        );
+    }
+
+    fn create_stack_args_function(call_conv: CallConv) -> Function {
+        let mut sig = Signature::new(call_conv);
+        sig.params
+            .extend((0..10).map(|_| AbiParam::new(types::I64)));
+        sig.returns.push(AbiParam::new(types::I64));
+        let mut func = Function::with_name_signature(UserFuncName::user(0, 0), sig);
+
+        let block0 = func.dfg.make_block();
+        for _ in 0..10 {
+            func.dfg.append_block_param(block0, types::I64);
+        }
+        let last = *func.dfg.block_params(block0).last().unwrap();
+
+        let mut pos = FuncCursor::new(&mut func);
+        pos.insert_block(block0);
+        pos.ins().return_(&[last]);
+
+        func
+    }
+
     fn create_function(call_conv: CallConv, stack_slot: Option<StackSlotData>) -> Function {
         let mut func =
             Function::with_name_signature(UserFuncName::user(0, 0), Signature::new(call_conv));
@@ -148,6 +202,398 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_shrink_wrapped_func() {
+        let isa = lookup(triple!("riscv64"))
+            .expect("expect riscv64 ISA")
+            .finish(Flags::new(builder()))
+            .expect("Creating compiler backend");
+
+        let mut context =
+            Context::for_function(create_guard_and_return_function(CallConv::SystemV));
+
+        let code = context
+            .compile(&*isa, &mut Default::default())
+            .expect("expected compilation");
+
+        let fde = match code
+            .create_unwind_info(isa.as_ref())
+            .expect("can create unwind info")
+        {
+            Some(crate::isa::unwind::UnwindInfo::SystemV(info)) => {
+                info.to_fde(Address::Constant(4321))
+            }
+            _ => panic!("expected unwind information"),
+        };
+
+        // The fast path returns before the prologue, so it is covered by the
+        // CIE's initial rules and every frame rule starts at the slow path,
+        // 12 bytes in.
+        assert_eq!(
+            format!("{fde:?}"),
+            "FrameDescriptionEntry { address: Constant(4321), length: 116, lsda: None, instructions: [(24, CfaOffset(16)), (24, Offset(Register(8), -16)), (24, Offset(Register(1), -8)), (28, CfaRegister(Register(8))), (36, Offset(Register(19), -24))] }"
+        );
+    }
+
+    #[test]
+    fn test_shrink_wrapped_func_with_frame_pointers() {
+        let mut flags = builder();
+        flags.set("preserve_frame_pointers", "true").unwrap();
+        let isa = lookup(triple!("riscv64"))
+            .expect("expect riscv64 ISA")
+            .finish(Flags::new(flags))
+            .expect("Creating compiler backend");
+
+        let mut context =
+            Context::for_function(create_guard_and_return_function(CallConv::SystemV));
+
+        let code = context
+            .compile(&*isa, &mut Default::default())
+            .expect("expected compilation");
+
+        let fde = match code
+            .create_unwind_info(isa.as_ref())
+            .expect("can create unwind info")
+        {
+            Some(crate::isa::unwind::UnwindInfo::SystemV(info)) => {
+                info.to_fde(Address::Constant(4321))
+            }
+            _ => panic!("expected unwind information"),
+        };
+
+        // The frame record is pushed on entry, so both paths unwind through
+        // it; the switch to an FP-based CFA and the clobber saves are only
+        // described from the slow path on, 40 bytes in.
+        assert_eq!(
+            format!("{fde:?}"),
+            "FrameDescriptionEntry { address: Constant(4321), length: 128, lsda: None, instructions: [(12, CfaOffset(16)), (12, Offset(Register(8), -16)), (12, Offset(Register(1), -8)), (40, CfaRegister(Register(8))), (48, Offset(Register(19), -24))] }"
+        );
+    }
+
+    fn create_guard_and_return_function(call_conv: CallConv) -> Function {
+        let mut sig = Signature::new(call_conv);
+        sig.params.push(AbiParam::new(types::I64));
+        sig.params.push(AbiParam::new(types::I64));
+        sig.returns.push(AbiParam::new(types::I64));
+        let mut func = Function::with_name_signature(UserFuncName::user(0, 0), sig);
+
+        let mut callee_sig = Signature::new(call_conv);
+        callee_sig.params.push(AbiParam::new(types::I64));
+        callee_sig.returns.push(AbiParam::new(types::I64));
+        let callee_sig = func.import_signature(callee_sig);
+        let callee = func.import_function(ExtFuncData {
+            name: ExternalName::user(UserExternalNameRef::new(0)),
+            signature: callee_sig,
+            colocated: false,
+        });
+
+        let block0 = func.dfg.make_block();
+        let v0 = func.dfg.append_block_param(block0, types::I64);
+        let v1 = func.dfg.append_block_param(block0, types::I64);
+        let block1 = func.dfg.make_block();
+        let block2 = func.dfg.make_block();
+
+        let mut pos = FuncCursor::new(&mut func);
+        pos.insert_block(block0);
+        pos.ins().brif(v0, block2, &[], block1, &[]);
+
+        pos.insert_block(block1);
+        pos.ins().return_(&[v1]);
+
+        pos.insert_block(block2);
+        let call = pos.ins().call(callee, &[v0]);
+        let v2 = pos.func.dfg.first_result(call);
+        let call = pos.ins().call(callee, &[v2]);
+        let v3 = pos.func.dfg.first_result(call);
+        let v4 = pos.ins().iadd(v3, v2);
+        pos.ins().return_(&[v4]);
+
+        func
+    }
+
+    #[test]
+    fn test_unwind_from_shrink_wrapped_fast_path() {
+        for preserve_frame_pointers in [false, true] {
+            let (code, traps) = compile_trapping_guard_and_return(preserve_frame_pointers, false);
+
+            // Fault on the load in the fast path, which doesn't touch the stack
+            // unless the frame record is set up on entry.
+            let (x, stack, pc) = run_until_trap(&code, 0, BAD_ADDR);
+            assert!(
+                traps.contains(&(pc, TrapCode::HEAP_OUT_OF_BOUNDS)),
+                "{traps:?}"
+            );
+            if !preserve_frame_pointers {
+                assert_eq!(x[2], STACK_TOP);
+            }
+            assert_caller_frame(&code, &x, &stack, pc);
+        }
+    }
+
+    #[test]
+    fn test_unwind_from_shrink_wrapped_body() {
+        for preserve_frame_pointers in [false, true] {
+            let (code, traps) = compile_trapping_guard_and_return(preserve_frame_pointers, false);
+
+            // Trap both in the island after the body and on a load in it.
+            for (a1, trap_code) in [
+                (0, TrapCode::user(1).unwrap()),
+                (1, TrapCode::HEAP_OUT_OF_BOUNDS),
+            ] {
+                let (x, stack, pc) = run_until_trap(&code, BAD_ADDR, a1);
+                assert!(traps.contains(&(pc, trap_code)), "{traps:?}");
+                assert_caller_frame(&code, &x, &stack, pc);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unwind_from_fast_path_trap_island() {
+        for preserve_frame_pointers in [false, true] {
+            let (code, traps) = compile_trapping_guard_and_return(preserve_frame_pointers, true);
+
+            // The conditional trap in the fast path branches to the island
+            // after the body, so it has to be covered by the full frame.
+            let (x, stack, pc) = run_until_trap(&code, 0, 0);
+            assert!(
+                traps.contains(&(pc, TrapCode::user(2).unwrap())),
+                "{traps:?}"
+            );
+            assert_caller_frame(&code, &x, &stack, pc);
+        }
+    }
+
+    /// Top of the emulated stack, which is the caller's stack pointer.
+    const STACK_TOP: u64 = 0x10_0000;
+    /// Size of the emulated stack.
+    const STACK_SIZE: u64 = 0x1000;
+    /// The caller's frame pointer and return address.
+    const CALLER_FP: u64 = 0xf00d_0000;
+    const CALLER_RA: u64 = 0xdead_0000;
+    /// An address outside of the emulated stack, so accessing it faults.
+    const BAD_ADDR: u64 = 0xbad_0000;
+
+    struct Compiled {
+        code: Vec<u8>,
+        fde: gimli::write::FrameDescriptionEntry,
+    }
+
+    /// Compiles the function from `create_trapping_guard_and_return_function`
+    /// and returns it along with its trap sites.
+    fn compile_trapping_guard_and_return(
+        preserve_frame_pointers: bool,
+        fast_path_trapz: bool,
+    ) -> (Compiled, Vec<(u64, TrapCode)>) {
+        let mut flags = builder();
+        let preserve_frame_pointers = if preserve_frame_pointers {
+            "true"
+        } else {
+            "false"
+        };
+        flags
+            .set("preserve_frame_pointers", preserve_frame_pointers)
+            .unwrap();
+        let isa = lookup(triple!("riscv64"))
+            .expect("expect riscv64 ISA")
+            .finish(Flags::new(flags))
+            .expect("Creating compiler backend");
+
+        let mut context = Context::for_function(create_trapping_guard_and_return_function(
+            CallConv::SystemV,
+            fast_path_trapz,
+        ));
+        let code = context
+            .compile(&*isa, &mut Default::default())
+            .expect("expected compilation");
+
+        let fde = match code
+            .create_unwind_info(isa.as_ref())
+            .expect("can create unwind info")
+        {
+            Some(crate::isa::unwind::UnwindInfo::SystemV(info)) => {
+                info.to_fde(Address::Constant(0))
+            }
+            _ => panic!("expected unwind information"),
+        };
+        let traps = code
+            .buffer
+            .traps()
+            .iter()
+            .map(|trap| (u64::from(trap.offset), trap.code))
+            .collect();
+        let code = Compiled {
+            code: code.code_buffer().to_vec(),
+            fde,
+        };
+        (code, traps)
+    }
+
+    /// Runs `code` from its start with `a0` and `a1` as the arguments until it
+    /// reaches a trap or faults on a memory access. Returns the registers, the
+    /// stack and the pc at that point.
+    ///
+    /// Only the instructions that the functions in these tests are made of are
+    /// supported.
+    fn run_until_trap(code: &Compiled, a0: u64, a1: u64) -> ([u64; 32], Vec<u8>, u64) {
+        let mut x = [0u64; 32];
+        x[1] = CALLER_RA;
+        x[2] = STACK_TOP;
+        x[8] = CALLER_FP;
+        x[10] = a0;
+        x[11] = a1;
+        let mut stack = vec![0u8; STACK_SIZE as usize];
+        let mut pc = 0u64;
+        loop {
+            let w = u32::from_le_bytes(code.code[pc as usize..][..4].try_into().unwrap());
+            if w == 0 {
+                // udf
+                return (x, stack, pc);
+            }
+            let rd = ((w >> 7) & 0x1f) as usize;
+            let rs1 = x[((w >> 15) & 0x1f) as usize];
+            let rs2 = x[((w >> 20) & 0x1f) as usize];
+            let imm_i = i64::from((w as i32) >> 20);
+            let imm_s = i64::from(((w & 0xfe00_0000) as i32 >> 20) | ((w >> 7) & 0x1f) as i32);
+            let mut next_pc = pc + 4;
+            match (w & 0x7f, (w >> 12) & 0x7) {
+                // addi
+                (0x13, 0) => x[rd] = rs1.wrapping_add_signed(imm_i),
+                // add
+                (0x33, 0) if w >> 25 == 0 => x[rd] = rs1.wrapping_add(rs2),
+                // ld
+                (0x03, 3) => match stack_slot(&mut stack, rs1.wrapping_add_signed(imm_i)) {
+                    Some(slot) => x[rd] = u64::from_le_bytes(*slot),
+                    None => return (x, stack, pc),
+                },
+                // sd
+                (0x23, 3) => match stack_slot(&mut stack, rs1.wrapping_add_signed(imm_s)) {
+                    Some(slot) => *slot = rs2.to_le_bytes(),
+                    None => return (x, stack, pc),
+                },
+                // beq, bne
+                (0x63, f3 @ (0 | 1)) => {
+                    let imm = ((w >> 31) << 12)
+                        | (((w >> 7) & 1) << 11)
+                        | (((w >> 25) & 0x3f) << 5)
+                        | (((w >> 8) & 0xf) << 1);
+                    let imm = ((imm << 19) as i32) >> 19;
+                    if (rs1 == rs2) == (f3 == 0) {
+                        next_pc = pc.wrapping_add_signed(imm.into());
+                    }
+                }
+                // jalr
+                (0x67, 0) => panic!("returned at {pc:#x} without trapping"),
+                _ => panic!("unexpected instruction {w:#010x} at {pc:#x}"),
+            }
+            x[0] = 0;
+            pc = next_pc;
+        }
+    }
+
+    /// Returns the 8 bytes of the emulated stack at `addr`, if they are all in
+    /// it.
+    fn stack_slot(stack: &mut [u8], addr: u64) -> Option<&mut [u8; 8]> {
+        let offset = addr.checked_sub(STACK_TOP - STACK_SIZE)?;
+        stack
+            .get_mut(usize::try_from(offset).ok()?..)?
+            .get_mut(..8)?
+            .try_into()
+            .ok()
+    }
+
+    /// Unwinds the frame of `code` that is stopped at `pc` with the registers
+    /// `x` and the stack `stack`, and checks that it recovers the caller's
+    /// stack pointer, frame pointer and return address.
+    fn assert_caller_frame(code: &Compiled, x: &[u64; 32], stack: &[u8], pc: u64) {
+        use gimli::{
+            BaseAddresses, CfaRule, LittleEndian, Register, RegisterRule, UnwindContext,
+            UnwindSection,
+        };
+
+        let mut table = gimli::write::FrameTable::default();
+        let cie = table.add_cie(super::create_cie());
+        table.add_fde(cie, code.fde.clone());
+        let mut section = gimli::write::DebugFrame(gimli::write::EndianVec::new(LittleEndian));
+        table.write_debug_frame(&mut section).unwrap();
+        let mut debug_frame = gimli::DebugFrame::new(section.slice(), LittleEndian);
+        debug_frame.set_address_size(8);
+
+        let mut ctx = UnwindContext::new();
+        let row = debug_frame
+            .unwind_info_for_address(
+                &BaseAddresses::default(),
+                &mut ctx,
+                pc,
+                gimli::DebugFrame::cie_from_offset,
+            )
+            .unwrap();
+        let cfa = match *row.cfa() {
+            CfaRule::RegisterAndOffset { register, offset } => {
+                x[usize::from(register.0)].wrapping_add_signed(offset)
+            }
+            ref rule => panic!("unexpected CFA rule {rule:?} at {pc:#x}"),
+        };
+        let restore = |reg: u16| match row.register(Register(reg)) {
+            RegisterRule::Undefined | RegisterRule::SameValue => x[usize::from(reg)],
+            RegisterRule::Offset(offset) => {
+                let addr = cfa.wrapping_add_signed(offset);
+                let offset = usize::try_from(addr - (STACK_TOP - STACK_SIZE)).unwrap();
+                u64::from_le_bytes(stack[offset..][..8].try_into().unwrap())
+            }
+            rule => panic!("unexpected rule {rule:?} for x{reg} at {pc:#x}"),
+        };
+        assert_eq!(
+            (cfa, restore(8), restore(1)),
+            (STACK_TOP, CALLER_FP, CALLER_RA),
+            "unwinding from {pc:#x}"
+        );
+    }
+
+    /// Like `create_guard_and_return_function`, but both paths can trap. The
+    /// fast path loads through its second argument, and the body, which needs
+    /// a stack slot, traps if the second argument is zero and otherwise loads
+    /// through the first one.
+    fn create_trapping_guard_and_return_function(
+        call_conv: CallConv,
+        fast_path_trapz: bool,
+    ) -> Function {
+        let mut sig = Signature::new(call_conv);
+        sig.params.push(AbiParam::new(types::I64));
+        sig.params.push(AbiParam::new(types::I64));
+        sig.returns.push(AbiParam::new(types::I64));
+        let mut func = Function::with_name_signature(UserFuncName::user(0, 0), sig);
+        let ss0 =
+            func.sized_stack_slots
+                .push(StackSlotData::new(StackSlotKind::ExplicitSlot, 8, 0));
+
+        let block0 = func.dfg.make_block();
+        let v0 = func.dfg.append_block_param(block0, types::I64);
+        let v1 = func.dfg.append_block_param(block0, types::I64);
+        let block1 = func.dfg.make_block();
+        let block2 = func.dfg.make_block();
+
+        let mut pos = FuncCursor::new(&mut func);
+        pos.insert_block(block0);
+        pos.ins().brif(v0, block2, &[], block1, &[]);
+
+        pos.insert_block(block1);
+        if fast_path_trapz {
+            pos.ins().trapz(v1, TrapCode::user(2).unwrap());
+        }
+        let v2 = pos.ins().load(types::I64, MemFlags::new(), v1, 0);
+        pos.ins().return_(&[v2]);
+
+        pos.insert_block(block2);
+        pos.ins().stack_store(v1, ss0, 0);
+        pos.ins().trapz(v1, TrapCode::user(1).unwrap());
+        let v3 = pos.ins().load(types::I64, MemFlags::new(), v0, 0);
+        let v4 = pos.ins().stack_load(types::I64, ss0, 0);
+        let v5 = pos.ins().iadd(v3, v4);
+        pos.ins().return_(&[v5]);
+
+        func
+    }
+
     fn create_multi_return_function(call_conv: CallConv) -> Function {
         let mut sig = Signature::new(call_conv);
         sig.params.push(AbiParam::new(types::I32));
@@ -1,7 +1,9 @@
 //! Lowering rules for Riscv64.
-use crate::ir::Inst as IRInst;
+use crate::ir::types::{F32, F64};
+use crate::ir::{Inst as IRInst, Value};
 use crate::isa::riscv64::Riscv64Backend;
 use crate::isa::riscv64::inst::*;
+use crate::isa::riscv64::lower::isle::generated_code::FpuOPWidth;
 use crate::machinst::lower::*;
 use crate::machinst::*;
 pub mod isle;
@@ -31,5 +33,42 @@ impl LowerBackend for Riscv64Backend {
         None
     }
 
+    fn maybe_remat_constant(&self, ctx: &mut Lower<Inst>, val: Value) -> Option<ValueRegs<Reg>> {
+        // Constants that are built by a single instruction are recomputed at
+        // each use, which is no more expensive than the move or reload that
+        // regalloc would otherwise insert for a long-lived one.
+        let def = ctx.dfg().value_def(val).inst()?;
+        let bits = ctx.get_constant(def)?;
+        let ty = ctx.dfg().value_type(val);
+        let rd = match ty {
+            F32 | F64 if bits == 0 => {
+                let rd = ctx.alloc_tmp(ty).only_reg().unwrap();
+                ctx.emit(Inst::FpuRR {
+                    alu_op: FpuOPRR::FmvFmtX,
+                    width: FpuOPWidth::try_from(ty).unwrap(),
+                    frm: FRM::RNE,
+                    rd,
+                    rs: zero_reg(),
+                });
+                rd
+            }
+            _ if ty.is_int() && ty.bits() <= 64 => {
+                // Narrow constants are sign-extended, matching `imm`.
+                let shift = 64 - ty.bits();
+                let value = (((bits << shift) as i64) >> shift) as u64;
+                let seq =
+                    ImmSeq::generate(value, self.isa_flags.has_zba(), self.isa_flags.has_zbs());
+                if seq.len() != 1 {
+                    return None;
+                }
+                let rd = ctx.alloc_tmp(ty).only_reg().unwrap();
+                ctx.emit(seq.ops()[0].gen_inst(rd, zero_reg()));
+                rd
+            }
+            _ => return None,
+        };
+        Some(ValueRegs::one(rd.to_reg()))
+    }
+
     type FactFlowState = ();
 }
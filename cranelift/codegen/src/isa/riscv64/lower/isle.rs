@@ -6,7 +6,9 @@ use generated_code::MInst;
 
 // Types that the generated ISLE code uses via `use super::*`.
 use self::generated_code::{FpuOPWidth, VecAluOpRR, VecLmul};
+use crate::isa::CallConv;
 use crate::isa::riscv64::Riscv64Backend;
+use crate::isa::riscv64::abi::{FRAME_RECORD_RA_OFFSET, Riscv64MachineDeps};
 use crate::isa::riscv64::lower::args::{
     FReg, VReg, WritableFReg, WritableVReg, WritableXReg, XReg,
 };
@@ -15,11 +17,12 @@ use crate::machinst::{CallInfo, MachInst, isle::*};
 use crate::machinst::{VCodeConstant, VCodeConstantData};
 use crate::{
     ir::{
-        AtomicRmwOp, BlockCall, ExternalName, Inst, InstructionData, MemFlags, Opcode, TrapCode,
-        Value, ValueList, immediates::*, types::*,
+        AtomicRmwOp, BlockCall, ExternalName, Inst, InstructionData, LibCall, MemFlags, Opcode,
+        TrapCode, Value, ValueList, immediates::*, types::*,
     },
     isa::riscv64::inst::*,
     machinst::{ArgPair, CallArgList, CallRetList, InstOutput},
+    settings::OptLevel,
 };
 use regalloc2::PReg;
 use std::boxed::Box;
@@ -279,21 +282,6 @@ impl generated_code::Context for RV64IsleContext<'_, '_, MInst, Riscv64Backend>
         }
     }
 
-    fn load_ra(&mut self) -> Reg {
-        if self.backend.flags.preserve_frame_pointers() {
-            let tmp = self.temp_writable_reg(I64);
-            self.emit(&MInst::Load {
-                rd: tmp,
-                op: LoadOP::Ld,
-                flags: MemFlags::trusted(),
-                from: AMode::FPOffset(8),
-            });
-            tmp.to_reg()
-        } else {
-            link_reg()
-        }
-    }
-
     fn label_to_br_target(&mut self, label: MachLabel) -> CondBrTarget {
         CondBrTarget::Label(label)
     }
@@ -321,27 +309,36 @@ impl generated_code::Context for RV64IsleContext<'_, '_, MInst, Riscv64Backend>
         MInst::generate_imm(imm as u64)
     }
 
-    fn i64_shift_for_lui(&mut self, imm: i64) -> Option<(u64, Imm12)> {
-        let trailing = imm.trailing_zeros();
-        if trailing < 12 {
-            return None;
+    fn imm_seq(&mut self, imm: i64) -> Option<ImmSeq> {
+        let seq = ImmSeq::generate(
+            imm as u64,
+            self.backend.isa_flags.has_zba(),
+            self.backend.isa_flags.has_zbs(),
+        );
+        // A constant pool load is an `auipc` and an `ld`, and the load's
+        // latency is typically a few cycles more than a single ALU operation.
+        // When optimizing for size as well, four instructions are as large as
+        // the load plus its 8-byte pool entry, which is shared by every use of
+        // the constant in the function, so favor the pool there.
+        let max_len = match self.backend.flags.opt_level() {
+            OptLevel::SpeedAndSize => 3,
+            OptLevel::None | OptLevel::Speed => 4,
+        };
+        if seq.len() <= max_len {
+            Some(seq)
+        } else {
+            None
         }
-
-        let shift = Imm12::from_i16(trailing as i16 - 12);
-        let base = (imm as u64) >> trailing;
-        Some((base, shift))
     }
 
-    fn i64_shift(&mut self, imm: i64) -> Option<(i64, Imm12)> {
-        let trailing = imm.trailing_zeros();
-        // We can do without this condition but in this case there is no need to go further
-        if trailing == 0 {
-            return None;
+    fn emit_imm_seq(&mut self, seq: ImmSeq) -> XReg {
+        let mut rs = zero_reg();
+        for op in seq.ops() {
+            let rd = self.temp_writable_reg(I64);
+            self.emit(&op.gen_inst(rd, rs));
+            rs = rd.to_reg();
         }
-
-        let shift = Imm12::from_i16(trailing as i16);
-        let base = imm >> trailing;
-        Some((base, shift))
+        XReg::new(rs).unwrap()
     }
 
     #[inline]
@@ -420,14 +417,6 @@ impl generated_code::Context for RV64IsleContext<'_, '_, MInst, Riscv64Backend>
             None
         }
     }
-    #[inline]
-    fn imm_from_bits(&mut self, val: u64) -> Imm12 {
-        Imm12::maybe_from_u64(val).unwrap()
-    }
-    #[inline]
-    fn imm_from_neg_bits(&mut self, val: i64) -> Imm12 {
-        Imm12::maybe_from_i64(val).unwrap()
-    }
 
     fn frm_bits(&mut self, frm: &FRM) -> UImm5 {
         UImm5::maybe_from_u8(frm.bits()).unwrap()
@@ -440,9 +429,6 @@ impl generated_code::Context for RV64IsleContext<'_, '_, MInst, Riscv64Backend>
             panic!("Unable to make an Imm12 value from {val}")
         }
     }
-    fn imm12_const_add(&mut self, val: i32, add: i32) -> Imm12 {
-        Imm12::maybe_from_i64((val + add) as i64).unwrap()
-    }
     fn imm12_add(&mut self, val: Imm12, add: i32) -> Option<Imm12> {
         Imm12::maybe_from_i64((i32::from(val.as_i16()) + add).into())
     }
@@ -524,6 +510,18 @@ impl generated_code::Context for RV64IsleContext<'_, '_, MInst, Riscv64Backend>
         self.backend.isa_flags.has_zicond()
     }
 
+    fn has_zacas(&mut self) -> bool {
+        self.backend.isa_flags.has_zacas()
+    }
+
+    fn emulate_unaligned_access(&mut self, flags: MemFlags) -> bool {
+        self.backend.isa_flags.emulate_unaligned() && !flags.aligned()
+    }
+
+    fn amode_offset_by(&mut self, amode: AMode, amount: i64) -> AMode {
+        amode.offset_by(amount)
+    }
+
     fn gen_reg_offset_amode(&mut self, base: Reg, offset: i64) -> AMode {
         AMode::RegOffset(base, offset)
     }
@@ -536,6 +534,10 @@ impl generated_code::Context for RV64IsleContext<'_, '_, MInst, Riscv64Backend>
         AMode::FPOffset(offset)
     }
 
+    fn frame_record_ra_offset(&mut self) -> i64 {
+        FRAME_RECORD_RA_OFFSET
+    }
+
     fn gen_stack_slot_amode(&mut self, ss: StackSlot, offset: i64) -> AMode {
         // Offset from beginning of stackslot area.
         let stack_off = self.lower_ctx.abi().sized_stackslot_offsets()[ss] as i64;
@@ -575,16 +577,49 @@ impl generated_code::Context for RV64IsleContext<'_, '_, MInst, Riscv64Backend>
     fn store_op(&mut self, ty: Type) -> StoreOP {
         StoreOP::from_type(ty)
     }
-    fn load_ext_name(&mut self, name: ExternalName, offset: i64) -> Reg {
+    fn load_ext_name(&mut self, name: ExternalName, offset: i64, distance: RelocDistance) -> Reg {
         let tmp = self.temp_writable_reg(I64);
         self.emit(&MInst::LoadExtName {
             rd: tmp,
             name: Box::new(name),
             offset,
+            distance,
         });
         tmp.to_reg()
     }
 
+    fn libcall_2(&mut self, libcall: &LibCall, a: ValueRegs, b: ValueRegs) -> ValueRegs {
+        let flags = &self.backend.flags;
+        let call_conv =
+            CallConv::for_libcall(flags, CallConv::triple_default(&self.backend.triple));
+        let sig = libcall.signature(call_conv, I64);
+        let outputs = self.lower_ctx.gen_call_output(&sig);
+
+        if !self.lower_ctx.sigs().have_abi_sig_for_signature(&sig) {
+            self.lower_ctx
+                .sigs_mut()
+                .make_abi_sig_from_ir_signature::<Riscv64MachineDeps>(sig.clone(), flags)
+                .expect("Failed to create LibCall signature");
+        }
+        let sig = self.lower_ctx.sigs().abi_sig_for_signature(&sig);
+
+        let uses = self.lower_ctx.gen_call_args(sig, &[a, b]);
+        let defs = self.lower_ctx.gen_call_rets(sig, &outputs);
+        let name = ExternalName::LibCall(*libcall);
+
+        if flags.use_colocated_libcalls() {
+            let info = self.gen_call_info(sig, name, uses, defs, None);
+            self.emit(&MInst::Call { info });
+        } else {
+            let target = self.load_ext_name(name, 0, RelocDistance::Far);
+            let info = self.gen_call_ind_info(sig, target, uses, defs, None);
+            self.emit(&MInst::CallInd { info });
+        }
+
+        debug_assert_eq!(outputs.len(), 1);
+        outputs[0]
+    }
+
     fn gen_stack_addr(&mut self, slot: StackSlot, offset: Offset32) -> Reg {
         let result = self.temp_writable_reg(I64);
         let i = self
@@ -594,7 +629,9 @@ impl generated_code::Context for RV64IsleContext<'_, '_, MInst, Riscv64Backend>
         self.emit(&i);
         result.to_reg()
     }
-    fn atomic_amo(&mut self) -> AMO {
+    fn atomic_amo(&mut self, _flags: MemFlags) -> AMO {
+        // CLIF atomics are all sequentially consistent: there is no way yet
+        // to ask for a weaker ordering through the memory flags.
         AMO::SeqCst
     }
 
@@ -574,7 +574,11 @@ pub trait ABIMachineSpec {
     ) -> u32;
 
     /// Get the ABI-dependent MachineEnv for managing register allocation.
-    fn get_machine_env(flags: &settings::Flags, call_conv: isa::CallConv) -> &MachineEnv;
+    fn get_machine_env(
+        flags: &settings::Flags,
+        isa_flags: &Self::F,
+        call_conv: isa::CallConv,
+    ) -> &'static MachineEnv;
 
     /// Get all caller-save registers, that is, registers that we expect
     /// not to be saved across a call to a callee with the given ABI.
@@ -604,6 +608,12 @@ pub trait ABIMachineSpec {
     fn exception_payload_regs(_call_conv: isa::CallConv) -> &'static [Reg] {
         &[]
     }
+
+    /// Returns true if `f` doesn't call any other functions, including the
+    /// libcalls that this backend lowers some instructions to.
+    fn is_leaf(f: &ir::Function) -> bool {
+        f.is_leaf()
+    }
 }
 
 /// Out-of-line data for calls, to keep the size of `Inst` down.
@@ -1303,7 +1313,7 @@ impl<M: ABIMachineSpec> Callee<M> {
             call_conv,
             flags,
             isa_flags: isa_flags.clone(),
-            is_leaf: f.is_leaf(),
+            is_leaf: M::is_leaf(f),
             stack_limit,
             _mach: PhantomData,
         })
@@ -1523,7 +1533,7 @@ impl<M: ABIMachineSpec> Callee<M> {
 
     /// Get the ABI-dependent MachineEnv for managing register allocation.
     pub fn machine_env(&self) -> &MachineEnv {
-        M::get_machine_env(&self.flags, self.call_conv)
+        M::get_machine_env(&self.flags, &self.isa_flags, self.call_conv)
     }
 
     /// The offsets of all sized stack slots (not spill slots) for debuginfo purposes.
@@ -2293,6 +2303,77 @@ impl<M: ABIMachineSpec> Callee<M> {
         insts
     }
 
+    /// Can the prologue be moved off the function's entry, post-regalloc?
+    ///
+    /// There has to be something to save or allocate for this to pay off.
+    /// Stack checks and growing the incoming argument area for tail calls
+    /// need scratch registers or the incoming arguments, so they can only run
+    /// on entry.
+    pub fn can_shrink_wrap(&self) -> bool {
+        let frame_layout = self.frame_layout();
+        (frame_layout.clobber_size > 0
+            || frame_layout.fixed_frame_storage_size > 0
+            || frame_layout.outgoing_args_size > 0)
+            && frame_layout.tail_args_size == 0
+            && self.stack_limit.is_none()
+            && !self.flags.enable_probestack()
+    }
+
+    /// Generate the part of the prologue of a shrink-wrapped function that
+    /// goes on its entry (`on_entry`) or at the start of the path that needs
+    /// the stack frame (`!on_entry`).
+    ///
+    /// The frame pointer setup stays on entry if frame pointers have to be
+    /// preserved, so that the frame can be walked from anywhere in the
+    /// function. The clobber saves and stack allocation always move.
+    pub fn gen_shrink_wrapped_prologue(&self, on_entry: bool) -> SmallInstVec<M::I> {
+        let frame_layout = self.frame_layout();
+        let mut insts = smallvec![];
+
+        if on_entry == self.flags.preserve_frame_pointers() {
+            insts.extend(M::gen_prologue_frame_setup(
+                self.call_conv,
+                &self.flags,
+                &self.isa_flags,
+                &frame_layout,
+            ));
+        }
+
+        if !on_entry {
+            insts.extend(M::gen_clobber_save(
+                self.call_conv,
+                &self.flags,
+                &frame_layout,
+            ));
+        }
+
+        insts
+    }
+
+    /// Generate the epilogue for a return that doesn't go through the part
+    /// of a shrink-wrapped function that sets up the stack frame.
+    pub fn gen_shrink_wrapped_epilogue(&self) -> SmallInstVec<M::I> {
+        let frame_layout = self.frame_layout();
+        let mut insts = smallvec![];
+
+        if self.flags.preserve_frame_pointers() {
+            insts.extend(M::gen_epilogue_frame_restore(
+                self.call_conv,
+                &self.flags,
+                &self.isa_flags,
+                &frame_layout,
+            ));
+        }
+
+        insts.extend(M::gen_return(
+            self.call_conv,
+            &self.isa_flags,
+            &frame_layout,
+        ));
+
+        insts
+    }
+
     /// Return a reference to the computed frame layout information. This
     /// function will panic if it's called before [`Self::compute_frame_layout`].
     pub fn frame_layout(&self) -> &FrameLayout {
@@ -261,6 +261,9 @@ pub struct MachBuffer<I: VCodeInst> {
     user_stack_maps: SmallVec<[(CodeOffset, u32, ir::UserStackMap); 8]>,
     /// Any unwind info at a given location.
     unwind_info: SmallVec<[(CodeOffset, UnwindInst); 8]>,
+    /// Ranges of `data` that hold data rather than instructions, sorted by
+    /// offset.
+    data_ranges: SmallVec<[Range<CodeOffset>; 4]>,
     /// The current source location in progress (after `start_srcloc()` and
     /// before `end_srcloc()`).  This is a (start_offset, src_loc) tuple.
     cur_srcloc: Option<(CodeOffset, RelSourceLoc)>,
@@ -340,6 +343,7 @@ impl MachBufferFinalized<Stencil> {
                 .collect(),
             user_stack_maps: self.user_stack_maps,
             unwind_info: self.unwind_info,
+            data_ranges: self.data_ranges,
             alignment: self.alignment,
         }
     }
@@ -374,6 +378,9 @@ pub struct MachBufferFinalized<T: CompilePhase> {
     pub(crate) user_stack_maps: SmallVec<[(CodeOffset, u32, ir::UserStackMap); 8]>,
     /// Any unwind info at a given location.
     pub unwind_info: SmallVec<[(CodeOffset, UnwindInst); 8]>,
+    /// Ranges of `data` that hold data rather than instructions, e.g.
+    /// constants, sorted by offset. Disassembly prints these as bytes.
+    pub(crate) data_ranges: SmallVec<[Range<CodeOffset>; 4]>,
     /// The required alignment of this buffer.
     pub alignment: u32,
 }
@@ -449,6 +456,7 @@ impl<I: VCodeInst> MachBuffer<I> {
             srclocs: SmallVec::new(),
             user_stack_maps: SmallVec::new(),
             unwind_info: SmallVec::new(),
+            data_ranges: SmallVec::new(),
             cur_srcloc: None,
             label_offsets: SmallVec::new(),
             label_aliases: SmallVec::new(),
@@ -517,6 +525,24 @@ impl<I: VCodeInst> MachBuffer<I> {
         // Post-invariant: as for `put1()`.
     }
 
+    /// Add a slice of bytes that is data rather than instructions, e.g. a
+    /// constant that the surrounding code jumps over.
+    pub fn put_inline_data(&mut self, data: &[u8]) {
+        let start = self.cur_offset();
+        self.put_data(data);
+        self.mark_data(start);
+    }
+
+    /// Record that everything from `start` up to the current offset is data.
+    fn mark_data(&mut self, start: CodeOffset) {
+        let end = self.cur_offset();
+        match self.data_ranges.last_mut() {
+            Some(last) if last.end == start => last.end = end,
+            _ if start < end => self.data_ranges.push(start..end),
+            _ => {}
+        }
+    }
+
     /// Reserve appended space and return a mutable slice referring to it.
     pub fn get_appended_space(&mut self, len: usize) -> &mut [u8] {
         let off = self.data.len();
@@ -1314,10 +1340,12 @@ impl<I: VCodeInst> MachBuffer<I> {
         for constant in mem::take(&mut self.pending_constants) {
             let MachBufferConstant { align, size, .. } = self.constants[constant];
             let label = self.constants[constant].upcoming_label.take().unwrap();
+            let start = self.cur_offset();
             self.align_to(align);
             self.bind_label(label, ctrl_plane);
             self.used_constants.push((constant, self.cur_offset()));
             self.get_appended_space(size);
+            self.mark_data(start);
         }
 
         // Either handle all pending fixups because they're ready or move them
@@ -1537,6 +1565,7 @@ impl<I: VCodeInst> MachBuffer<I> {
             srclocs,
             user_stack_maps: self.user_stack_maps,
             unwind_info: self.unwind_info,
+            data_ranges: self.data_ranges,
             alignment,
         }
     }
@@ -121,7 +121,7 @@ macro_rules! isle_lower_prelude_methods {
 
         #[inline]
         fn put_in_regs(&mut self, val: Value) -> ValueRegs {
-            self.lower_ctx.put_value_in_regs(val)
+            self.lower_ctx.put_value_in_regs_or_remat(self.backend, val)
         }
 
         #[inline]
@@ -150,6 +150,23 @@ pub trait LowerBackend {
         None
     }
 
+    /// Give the backend a chance to rematerialize the constant `val` at the
+    /// current use site instead of reading the register it was lowered into.
+    ///
+    /// This is meant for constants that are cheaper to recompute than to keep
+    /// live, such as ones built by a single instruction. Otherwise a constant
+    /// that is live across a call occupies a callee-saved register or a spill
+    /// slot. Any instructions must be emitted into `ctx`, and the returned
+    /// registers must hold the constant's value. `None` falls back to the
+    /// value's original registers.
+    fn maybe_remat_constant(
+        &self,
+        _ctx: &mut Lower<Self::MInst>,
+        _val: Value,
+    ) -> Option<ValueRegs<Reg>> {
+        None
+    }
+
     /// The type of state carried between `check_fact` invocations.
     type FactFlowState: Default + Clone + Debug;
 
@@ -224,6 +241,10 @@ pub struct Lower<'func, I: VCodeInst> {
     /// Instructions collected for the CLIF inst in progress, in forward order.
     ir_insts: Vec<I>,
 
+    /// Constants rematerialized for the CLIF inst in progress, so that
+    /// repeated uses within one instruction share a single copy.
+    remat_regs: FxHashMap<Value, ValueRegs<Reg>>,
+
     /// Try-call block arg normal-return values, indexed by instruction.
     try_call_rets: FxHashMap<Inst, SmallVec<[ValueRegs<Writable<Reg>>; 2]>>,
 
@@ -520,6 +541,7 @@ impl<'func, I: VCodeInst> Lower<'func, I> {
             cur_scan_entry_color: None,
             cur_inst: None,
             ir_insts: vec![],
+            remat_regs: FxHashMap::default(),
             try_call_rets,
             try_call_payloads,
             pinned_reg: None,
@@ -979,6 +1001,7 @@ impl<'func, I: VCodeInst> Lower<'func, I> {
         for inst in self.ir_insts.drain(..).rev() {
             self.vcode.push(inst, loc);
         }
+        self.remat_regs.clear();
     }
 
     fn finish_bb(&mut self) {
@@ -1609,6 +1632,24 @@ impl<'func, I: VCodeInst> Lower<'func, I> {
         regs
     }
 
+    /// Like `put_value_in_regs`, but first gives `backend` a chance to
+    /// rematerialize a constant at this use. See
+    /// [`LowerBackend::maybe_remat_constant`].
+    pub fn put_value_in_regs_or_remat<B: LowerBackend<MInst = I>>(
+        &mut self,
+        backend: &B,
+        val: Value,
+    ) -> ValueRegs<Reg> {
+        if let Some(&regs) = self.remat_regs.get(&val) {
+            return regs;
+        }
+        if let Some(regs) = backend.maybe_remat_constant(self, val) {
+            self.remat_regs.insert(val, regs);
+            return regs;
+        }
+        self.put_value_in_regs(val)
+    }
+
     /// Get the ValueRegs for the edge-defined values for special
     /// try-call-return block arguments.
     pub fn try_call_return_defs(&mut self, ir_inst: Inst) -> &[ValueRegs<Writable<Reg>>] {
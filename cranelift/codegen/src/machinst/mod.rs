@@ -207,6 +207,16 @@ pub trait MachInst: Clone + Debug {
         false
     }
 
+    /// Can this instruction run before the prologue? It must not call
+    /// anything or access the stack frame, e.g. stack slots, spill slots or
+    /// incoming stack arguments.
+    ///
+    /// Backends opt in to shrink-wrapping of the prologue by returning `true`
+    /// here for the instructions that qualify.
+    fn is_frame_free(&self) -> bool {
+        false
+    }
+
     /// A label-use kind: a type that describes the types of label references that
     /// can occur in an instruction.
     type LabelUse: MachInstLabelUse;
@@ -399,6 +409,28 @@ impl<T: CompilePhase> CompiledCodeBase<T> {
         let relocs = self.buffer.relocs();
         let traps = self.buffer.traps();
 
+        // Finishes the line for the bytes in `start..end` with the relocations
+        // and the trap they have.
+        let write_annotations = |buf: &mut String, start: CodeOffset, end: CodeOffset| {
+            let contains = |off| start <= off && off < end;
+
+            for reloc in relocs.iter().filter(|reloc| contains(reloc.offset)) {
+                write!(
+                    buf,
+                    " ; reloc_external {} {} {}",
+                    reloc.kind,
+                    reloc.target.display(params),
+                    reloc.addend,
+                )?;
+            }
+
+            if let Some(trap) = traps.iter().find(|trap| contains(trap.offset)) {
+                write!(buf, " ; trap: {}", trap.code)?;
+            }
+
+            writeln!(buf)
+        };
+
         // Normalize the block starts to include an initial block of offset 0.
         let mut block_starts = Vec::new();
         if self.bb_starts.first().copied() != Some(0) {
@@ -415,39 +447,52 @@ impl<T: CompilePhase> CompiledCodeBase<T> {
         {
             writeln!(buf, "block{n}: ; offset 0x{start:x}")?;
 
-            let buffer = &self.buffer.data()[start as usize..end as usize];
-            let insns = cs.disasm_all(buffer, start as u64).map_err(map_caperr)?;
-            for i in insns.iter() {
-                write!(buf, "  ")?;
-
-                let op_str = i.op_str().unwrap_or("");
-                if let Some(s) = i.mnemonic() {
-                    write!(buf, "{s}")?;
-                    if !op_str.is_empty() {
-                        write!(buf, " ")?;
+            // Split the block into code, which is disassembled, and data,
+            // which is printed as aligned 32-bit words, with any unaligned
+            // bytes at either end printed on their own.
+            let mut offset = start;
+            while offset < end {
+                let data = self
+                    .buffer
+                    .data_ranges
+                    .iter()
+                    .find(|range| range.end > offset && range.start < end);
+                let code_end = data.map_or(end, |range| range.start.max(offset));
+
+                let code = &self.buffer.data()[offset as usize..code_end as usize];
+                let insns = cs.disasm_all(code, offset.into()).map_err(map_caperr)?;
+                for i in insns.iter() {
+                    write!(buf, "  ")?;
+
+                    let op_str = i.op_str().unwrap_or("");
+                    if let Some(s) = i.mnemonic() {
+                        write!(buf, "{s}")?;
+                        if !op_str.is_empty() {
+                            write!(buf, " ")?;
+                        }
                     }
-                }
 
-                write!(buf, "{op_str}")?;
+                    write!(buf, "{op_str}")?;
 
-                let end = i.address() + i.bytes().len() as u64;
-                let contains = |off| i.address() <= off && off < end;
-
-                for reloc in relocs.iter().filter(|reloc| contains(reloc.offset as u64)) {
-                    write!(
-                        buf,
-                        " ; reloc_external {} {} {}",
-                        reloc.kind,
-                        reloc.target.display(params),
-                        reloc.addend,
-                    )?;
+                    let start = i.address() as CodeOffset;
+                    write_annotations(&mut buf, start, start + i.bytes().len() as CodeOffset)?;
                 }
 
-                if let Some(trap) = traps.iter().find(|trap| contains(trap.offset as u64)) {
-                    write!(buf, " ; trap: {}", trap.code)?;
+                offset = code_end;
+                let Some(data) = data else { continue };
+                let data_end = data.end.min(end);
+                while offset < data_end {
+                    let row_end = ((offset & !3) + 4).min(data_end);
+                    let bytes = &self.buffer.data()[offset as usize..row_end as usize];
+                    if let Ok(word) = <[u8; 4]>::try_from(bytes) {
+                        write!(buf, "  .word 0x{:08x}", u32::from_le_bytes(word))?;
+                    } else {
+                        let bytes: Vec<_> = bytes.iter().map(|b| format!("0x{b:02x}")).collect();
+                        write!(buf, "  .byte {}", bytes.join(", "))?;
+                    }
+                    write_annotations(&mut buf, offset, row_end)?;
+                    offset = row_end;
                 }
-
-                writeln!(buf)?;
             }
         }
 
@@ -26,8 +26,8 @@ use crate::trace;
 use crate::{LabelValueLoc, ValueLocRange};
 use crate::{machinst::*, trace_log_enabled};
 use regalloc2::{
-    Edit, Function as RegallocFunction, InstOrEdit, InstPosition, InstRange, Operand,
-    OperandConstraint, OperandKind, PRegSet, ProgPoint, RegClass,
+    Allocation, Edit, Function as RegallocFunction, InstOrEdit, InstPosition, InstRange, Operand,
+    OperandConstraint, OperandKind, PReg, PRegSet, ProgPoint, RegClass,
 };
 use rustc_hash::FxHashMap;
 
@@ -726,6 +726,66 @@ impl<I: VCodeInst> VCode<I> {
             .collect()
     }
 
+    /// Find the block that returns without needing the prologue and the block
+    /// to move the prologue to, if the function can be shrink-wrapped.
+    ///
+    /// This handles a guard-and-return shape: the entry block branches either
+    /// to a block that only returns or to the rest of the function, and
+    /// neither the entry block nor the returning block needs the stack frame
+    /// or a callee-saved register. The prologue then only runs on the way
+    /// into the rest of the function. The blocks before the prologue in the
+    /// emission order must be exactly those two, so that the unwind info
+    /// describing the prologue only covers code that runs after it.
+    fn shrink_wrap_block(
+        &self,
+        regalloc: &regalloc2::Output,
+        final_order: &[BlockIndex],
+    ) -> Option<(BlockIndex, BlockIndex)> {
+        if !self.abi.can_shrink_wrap() {
+            return None;
+        }
+
+        let [entry, ret_block, body, ..] = *final_order else {
+            return None;
+        };
+        debug_assert_eq!(entry, self.entry);
+        let succs = self.block_succs(entry);
+        if succs.len() != 2
+            || !succs.contains(&ret_block)
+            || !succs.contains(&body)
+            || self.block_preds(ret_block) != [entry]
+            || self.block_preds(body) != [entry]
+        {
+            return None;
+        }
+        let ret_insns = self.block_insns(ret_block);
+        if self.insts[ret_insns.last().index()].is_term() != MachTerminator::Ret {
+            return None;
+        }
+
+        let callee_saves = &self.abi.frame_layout().clobbered_callee_saves;
+        let is_free = |alloc: Allocation| match alloc.as_reg() {
+            Some(preg) => !callee_saves.iter().any(|r| PReg::from(r.to_reg()) == preg),
+            None => alloc.is_none(),
+        };
+        for block in [entry, ret_block] {
+            for inst_or_edit in regalloc.block_insts_and_edits(self, block) {
+                let ok = match inst_or_edit {
+                    InstOrEdit::Inst(iix) => {
+                        self.insts[iix.index()].is_frame_free()
+                            && regalloc.inst_allocs(iix).iter().all(|a| is_free(*a))
+                    }
+                    InstOrEdit::Edit(&Edit::Move { from, to }) => is_free(from) && is_free(to),
+                };
+                if !ok {
+                    return None;
+                }
+            }
+        }
+
+        Some((ret_block, body))
+    }
+
     /// Emit the instructions to a `MachBuffer`, containing fixed-up
     /// code and external reloc/trap/etc. records ready for use. Takes
     /// the regalloc results as well.
@@ -780,6 +840,7 @@ impl<I: VCodeInst> VCode<I> {
         let clobbers = self.compute_clobbers(regalloc);
         self.abi
             .compute_frame_layout(&self.sigs, regalloc.num_spillslots, clobbers);
+        let shrink_wrap = self.shrink_wrap_block(regalloc, &final_order);
 
         // Emit blocks.
         let mut cur_srcloc = None;
@@ -846,7 +907,11 @@ impl<I: VCodeInst> VCode<I> {
             if block == self.entry {
                 trace!(" -> entry block");
                 buffer.start_srcloc(Default::default());
-                for inst in &self.abi.gen_prologue() {
+                let prologue = match shrink_wrap {
+                    Some(_) => self.abi.gen_shrink_wrapped_prologue(true),
+                    None => self.abi.gen_prologue(),
+                };
+                for inst in &prologue {
                     do_emit(&inst, &mut disasm, &mut buffer, &mut state);
                 }
                 buffer.end_srcloc();
@@ -883,6 +948,17 @@ impl<I: VCodeInst> VCode<I> {
                 do_emit(&block_start, &mut disasm, &mut buffer, &mut state);
             }
 
+            // The rest of a shrink-wrapped function starts with the prologue
+            // that was left out of the entry block.
+            if shrink_wrap.is_some_and(|(_, body)| body == block) {
+                trace!(" -> shrink-wrapped prologue");
+                buffer.start_srcloc(Default::default());
+                for inst in &self.abi.gen_shrink_wrapped_prologue(false) {
+                    do_emit(&inst, &mut disasm, &mut buffer, &mut state);
+                }
+                buffer.end_srcloc();
+            }
+
             for inst_or_edit in regalloc.block_insts_and_edits(&self, block) {
                 match inst_or_edit {
                     InstOrEdit::Inst(iix) => {
@@ -943,7 +1019,13 @@ impl<I: VCodeInst> VCode<I> {
                         // (and don't emit the return; the actual
                         // epilogue will contain it).
                         if self.insts[iix.index()].is_term() == MachTerminator::Ret {
-                            for inst in self.abi.gen_epilogue() {
+                            let epilogue = match shrink_wrap {
+                                Some((ret_block, _)) if ret_block == block => {
+                                    self.abi.gen_shrink_wrapped_epilogue()
+                                }
+                                _ => self.abi.gen_epilogue(),
+                            };
+                            for inst in epilogue {
                                 do_emit(&inst, &mut disasm, &mut buffer, &mut state);
                             }
                         } else {
@@ -109,6 +109,17 @@ impl TestFileCompiler {
             }));
         }
 
+        #[cfg(target_arch = "riscv64")]
+        {
+            builder.symbol_lookup_fn(Box::new(|name| match name {
+                "__udivti3" => Some(__cranelift_udivti3 as *const u8),
+                "__divti3" => Some(__cranelift_divti3 as *const u8),
+                "__umodti3" => Some(__cranelift_umodti3 as *const u8),
+                "__modti3" => Some(__cranelift_modti3 as *const u8),
+                _ => None,
+            }));
+        }
+
         // On Unix platforms force `libm` to get linked into this executable
         // because tests that use libcalls rely on this library being present.
         // Without this it's been seen that when cross-compiled to riscv64 the
@@ -737,6 +748,30 @@ extern "C" fn __cranelift_x86_pshufb(a: __m128i, b: __m128i) -> __m128i {
     }
 }
 
+// The 128-bit division libcalls are provided by the compiler's runtime library
+// but are not necessarily exported from this executable, so provide our own
+// definitions for the JIT to resolve them to. The generated code has already
+// checked for a zero divisor and for signed overflow before calling these.
+#[cfg(target_arch = "riscv64")]
+extern "C" fn __cranelift_udivti3(a: u128, b: u128) -> u128 {
+    a / b
+}
+
+#[cfg(target_arch = "riscv64")]
+extern "C" fn __cranelift_divti3(a: i128, b: i128) -> i128 {
+    a.wrapping_div(b)
+}
+
+#[cfg(target_arch = "riscv64")]
+extern "C" fn __cranelift_umodti3(a: u128, b: u128) -> u128 {
+    a % b
+}
+
+#[cfg(target_arch = "riscv64")]
+extern "C" fn __cranelift_modti3(a: i128, b: i128) -> i128 {
+    a.wrapping_rem(b)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -743,11 +743,6 @@ fn valid_for_target(triple: &Triple, op: Opcode, args: &[Type], rets: &[Type]) -
                 (Opcode::UsubOverflow | Opcode::SsubOverflow),
                 (Opcode::UmulOverflow | Opcode::SmulOverflow),
                 // TODO
-                (
-                    Opcode::Udiv | Opcode::Sdiv | Opcode::Urem | Opcode::Srem,
-                    &[I128, I128]
-                ),
-                // TODO
                 (Opcode::Iabs, &[I128]),
                 // TODO
                 (Opcode::Bitselect, &[I128, I128, I128]),
@@ -130,6 +130,30 @@ impl CompiledBlob {
                         modify_inst32(jalr_addr, |jalr| (jalr & 0xFFFFF) | (lo12 << 20));
                     }
                 }
+                Reloc::RiscvPCRelHi20 => {
+                    // Colocated symbols are addressed with an `auipc`+`addi` pair, where the
+                    // `addi` carries a R_RISCV_PCREL_LO12_I pointing back at the `auipc`. The
+                    // pair is always emitted back to back, so we patch both halves here, in
+                    // the same way as for `RiscvCallPlt` above.
+                    let base = get_address(name);
+                    let what = unsafe { base.offset(isize::try_from(addend).unwrap()) };
+                    let pcrel = i32::try_from((what as isize) - (at as isize)).unwrap() as u32;
+
+                    let hi20 = pcrel.wrapping_add(0x800) & 0xFFFFF000;
+                    let lo12 = pcrel.wrapping_sub(hi20) & 0xFFF;
+
+                    unsafe {
+                        let auipc_addr = at as *mut u32;
+                        modify_inst32(auipc_addr, |auipc| (auipc & 0xFFF) | hi20);
+
+                        let addi_addr = at.offset(4) as *mut u32;
+                        modify_inst32(addi_addr, |addi| (addi & 0xFFFFF) | (lo12 << 20));
+                    }
+                }
+                Reloc::RiscvPCRelLo12I => {
+                    // Already applied along with the `RiscvPCRelHi20` of the `auipc` this
+                    // relocation points at.
+                }
                 _ => unimplemented!(),
             }
         }
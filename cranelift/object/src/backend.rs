@@ -909,6 +909,56 @@ impl ObjectModule {
                     r_type: object::elf::R_RISCV_PCREL_LO12_I,
                 }
             }
+            Reloc::RiscvPCRelHi20 => {
+                assert_eq!(
+                    self.object.format(),
+                    object::BinaryFormat::Elf,
+                    "RiscvPCRelHi20 is not supported for this file format"
+                );
+                RelocationFlags::Elf {
+                    r_type: object::elf::R_RISCV_PCREL_HI20,
+                }
+            }
+            Reloc::RiscvTlsGotHi20 => {
+                assert_eq!(
+                    self.object.format(),
+                    object::BinaryFormat::Elf,
+                    "RiscvTlsGotHi20 is not supported for this file format"
+                );
+                RelocationFlags::Elf {
+                    r_type: object::elf::R_RISCV_TLS_GOT_HI20,
+                }
+            }
+            Reloc::RiscvTPRelHi20 => {
+                assert_eq!(
+                    self.object.format(),
+                    object::BinaryFormat::Elf,
+                    "RiscvTPRelHi20 is not supported for this file format"
+                );
+                RelocationFlags::Elf {
+                    r_type: object::elf::R_RISCV_TPREL_HI20,
+                }
+            }
+            Reloc::RiscvTPRelAdd => {
+                assert_eq!(
+                    self.object.format(),
+                    object::BinaryFormat::Elf,
+                    "RiscvTPRelAdd is not supported for this file format"
+                );
+                RelocationFlags::Elf {
+                    r_type: object::elf::R_RISCV_TPREL_ADD,
+                }
+            }
+            Reloc::RiscvTPRelLo12I => {
+                assert_eq!(
+                    self.object.format(),
+                    object::BinaryFormat::Elf,
+                    "RiscvTPRelLo12I is not supported for this file format"
+                );
+                RelocationFlags::Elf {
+                    r_type: object::elf::R_RISCV_TPREL_LO12_I,
+                }
+            }
             Reloc::RiscvGotHi20 => {
                 assert_eq!(
                     self.object.format(),
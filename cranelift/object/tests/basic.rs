@@ -239,3 +239,331 @@ fn reject_nul_byte_symbol_for_data() {
         )
         .unwrap();
 }
+
+/// A relocation in the `.text` section of an object built by
+/// `build_riscv64_object`.
+#[derive(Debug)]
+struct Riscv64Reloc {
+    offset: u64,
+    r_type: u32,
+    symbol: String,
+    symbol_address: u64,
+    symbol_is_definition: bool,
+    addend: i64,
+}
+
+/// Builds a riscv64 object exporting a single function named `function`
+/// which returns `returns` and whose body is built by `body`, and returns the
+/// relocations and contents of its `.text` section.
+///
+/// `flags` are applied to the shared settings and the `isa_flags` named
+/// riscv64 settings are enabled.
+fn build_riscv64_object(
+    flags: &[(&str, &str)],
+    isa_flags: &[&str],
+    returns: &[Type],
+    body: impl FnOnce(&mut ObjectModule, &mut FunctionBuilder),
+) -> (Vec<Riscv64Reloc>, Vec<u8>) {
+    use object::{Object, ObjectSection, ObjectSymbol, RelocationFlags, RelocationTarget};
+    use settings::Configurable;
+
+    let mut flag_builder = settings::builder();
+    for (name, value) in flags {
+        flag_builder.set(name, value).unwrap();
+    }
+    let mut isa_builder =
+        cranelift_codegen::isa::lookup_by_name("riscv64gc-unknown-linux-gnu").unwrap();
+    for name in isa_flags {
+        isa_builder.enable(name).unwrap();
+    }
+    let isa = isa_builder
+        .finish(settings::Flags::new(flag_builder))
+        .unwrap();
+    let mut module =
+        ObjectModule::new(ObjectBuilder::new(isa, "foo", default_libcall_names()).unwrap());
+
+    let sig = Signature {
+        params: vec![],
+        returns: returns.iter().copied().map(AbiParam::new).collect(),
+        call_conv: CallConv::SystemV,
+    };
+    let func_id = module
+        .declare_function("function", Linkage::Export, &sig)
+        .unwrap();
+
+    let mut ctx = Context::new();
+    ctx.func = Function::with_name_signature(UserFuncName::user(0, func_id.as_u32()), sig);
+    let mut func_ctx = FunctionBuilderContext::new();
+    {
+        let mut bcx: FunctionBuilder = FunctionBuilder::new(&mut ctx.func, &mut func_ctx);
+        let block = bcx.create_block();
+        bcx.switch_to_block(block);
+        body(&mut module, &mut bcx);
+    }
+    module.define_function(func_id, &mut ctx).unwrap();
+
+    let bytes = module.finish().emit().unwrap();
+    let file = object::File::parse(&bytes[..]).unwrap();
+    let text = file.section_by_name(".text").unwrap();
+    let relocs = text
+        .relocations()
+        .map(|(offset, reloc)| {
+            let RelocationFlags::Elf { r_type } = reloc.flags() else {
+                panic!("unexpected relocation flags {:?}", reloc.flags());
+            };
+            let RelocationTarget::Symbol(symbol) = reloc.target() else {
+                panic!("unexpected relocation target {:?}", reloc.target());
+            };
+            let symbol = file.symbol_by_index(symbol).unwrap();
+            Riscv64Reloc {
+                offset,
+                r_type,
+                symbol: symbol.name().unwrap().to_string(),
+                symbol_address: symbol.address(),
+                symbol_is_definition: symbol.is_definition(),
+                addend: reloc.addend(),
+            }
+        })
+        .collect();
+    (relocs, text.data().unwrap().to_vec())
+}
+
+/// Returns the targets of the relocations of type `r_type`, in order.
+fn riscv64_reloc_targets(relocs: &[Riscv64Reloc], r_type: u32) -> Vec<&str> {
+    relocs
+        .iter()
+        .filter(|r| r.r_type == r_type)
+        .map(|r| r.symbol.as_str())
+        .collect()
+}
+
+#[test]
+fn riscv64_pic_symbol_relocations() {
+    let (relocs, _) =
+        build_riscv64_object(&[("is_pic", "true")], &[], &[types::I64], |module, bcx| {
+            let local = module
+                .declare_data("local_data", Linkage::Local, false, false)
+                .unwrap();
+            let mut data = DataDescription::new();
+            data.define_zeroinit(64);
+            module.define_data(local, &data).unwrap();
+            let imported = module
+                .declare_data("imported_data", Linkage::Import, false, false)
+                .unwrap();
+
+            let local = module.declare_data_in_func(local, &mut bcx.func);
+            let imported = module.declare_data_in_func(imported, &mut bcx.func);
+            let imported = bcx.func.create_global_value(GlobalValueData::IAddImm {
+                base: imported,
+                offset: 8.into(),
+                global_type: types::I64,
+            });
+            let a = bcx.ins().symbol_value(types::I64, local);
+            let b = bcx.ins().global_value(types::I64, imported);
+            let sum = bcx.ins().iadd(a, b);
+            bcx.ins().return_(&[sum]);
+        });
+
+    // The local data is addressed pc-relative while the imported one goes
+    // through the GOT, with the offset applied after loading the GOT entry.
+    assert_eq!(
+        riscv64_reloc_targets(&relocs, object::elf::R_RISCV_PCREL_HI20),
+        ["local_data"],
+        "{relocs:?}"
+    );
+    let got_hi = relocs
+        .iter()
+        .filter(|r| r.r_type == object::elf::R_RISCV_GOT_HI20)
+        .collect::<Vec<_>>();
+    assert_eq!(got_hi.len(), 1, "{relocs:?}");
+    assert_eq!(got_hi[0].symbol, "imported_data");
+    assert_eq!(got_hi[0].addend, 0);
+    assert_eq!(
+        riscv64_reloc_targets(&relocs, object::elf::R_RISCV_PCREL_LO12_I).len(),
+        2,
+        "{relocs:?}"
+    );
+}
+
+#[test]
+fn riscv64_colocated_function_relocations() {
+    let (relocs, _) = build_riscv64_object(&[], &[], &[types::I64], |module, bcx| {
+        let local = define_simple_function(module);
+        let callee_sig = module
+            .declarations()
+            .get_function_decl(local)
+            .signature
+            .clone();
+        let imported = module
+            .declare_function("imported", Linkage::Import, &callee_sig)
+            .unwrap();
+
+        let local = module.declare_func_in_func(local, &mut bcx.func);
+        let imported = module.declare_func_in_func(imported, &mut bcx.func);
+        bcx.ins().call(local, &[]);
+        bcx.ins().call(imported, &[]);
+        let a = bcx.ins().func_addr(types::I64, local);
+        let b = bcx.ins().func_addr(types::I64, imported);
+        let sum = bcx.ins().iadd(a, b);
+        bcx.ins().return_(&[sum]);
+    });
+
+    // The colocated function is called directly and addressed pc-relative,
+    // while the imported one is materialized from an absolute address both
+    // for the call and for `func_addr`.
+    let find = |r_type| riscv64_reloc_targets(&relocs, r_type);
+    assert_eq!(find(object::elf::R_RISCV_CALL_PLT), ["abc"], "{relocs:?}");
+    assert_eq!(find(object::elf::R_RISCV_PCREL_HI20), ["abc"], "{relocs:?}");
+    assert_eq!(
+        find(object::elf::R_RISCV_PCREL_LO12_I).len(),
+        1,
+        "{relocs:?}"
+    );
+    assert_eq!(
+        find(object::elf::R_RISCV_64),
+        ["imported", "imported"],
+        "{relocs:?}"
+    );
+}
+
+#[test]
+fn riscv64_call_plt_sequence() {
+    let (relocs, code) = build_riscv64_object(&[], &["has_c"], &[], |module, bcx| {
+        let local = define_simple_function(module);
+        let local = module.declare_func_in_func(local, &mut bcx.func);
+        bcx.ins().call(local, &[]);
+        bcx.ins().return_(&[]);
+    });
+    let calls = relocs
+        .iter()
+        .filter(|r| r.r_type == object::elf::R_RISCV_CALL_PLT)
+        .collect::<Vec<_>>();
+    assert_eq!(calls.len(), 1, "{relocs:?}");
+
+    // Linkers resolve and relax `R_RISCV_CALL_PLT` as an `auipc ra` directly
+    // followed by a `jalr ra`, both uncompressed even with the C extension
+    // enabled, and with the offset left entirely to the relocation.
+    let call = calls[0];
+    assert_eq!(call.symbol, "abc");
+    assert_eq!(call.addend, 0);
+    let word = |at: u64| u32::from_le_bytes(code[at as usize..][..4].try_into().unwrap());
+    assert_eq!(word(call.offset), 0x0000_0097, "auipc ra, 0");
+    assert_eq!(word(call.offset + 4), 0x0000_80e7, "jalr ra, 0(ra)");
+}
+
+#[test]
+fn riscv64_pcrel_symbol_round_trip() {
+    let (relocs, _) = build_riscv64_object(&[], &[], &[types::I64], |module, bcx| {
+        let local = module
+            .declare_data("local_data", Linkage::Local, false, false)
+            .unwrap();
+        let mut data = DataDescription::new();
+        data.define_zeroinit(64);
+        module.define_data(local, &data).unwrap();
+
+        let local = module.declare_data_in_func(local, &mut bcx.func);
+        let GlobalValueData::Symbol { offset, .. } = &mut bcx.func.global_values[local] else {
+            unreachable!()
+        };
+        *offset = 16.into();
+        let addr = bcx.ins().symbol_value(types::I64, local);
+        bcx.ins().return_(&[addr]);
+    });
+    assert_eq!(relocs.len(), 2, "{relocs:?}");
+
+    // The `auipc` refers to the data symbol itself with the offset folded into
+    // the addend.
+    let hi = &relocs[0];
+    assert_eq!(hi.r_type, object::elf::R_RISCV_PCREL_HI20);
+    assert_eq!(hi.symbol, "local_data");
+    assert!(hi.symbol_is_definition);
+    assert_eq!(hi.addend, 16);
+
+    // The `addi` following it refers back to the `auipc`. The `.text` section
+    // starts at address 0 in a relocatable object.
+    let lo = &relocs[1];
+    assert_eq!(lo.r_type, object::elf::R_RISCV_PCREL_LO12_I);
+    assert_eq!(lo.offset, hi.offset + 4);
+    assert_eq!(lo.symbol_address.wrapping_add(lo.addend as u64), hi.offset);
+}
+
+/// Compiles a function returning the address of an imported TLS symbol with
+/// the given `tls_model` and returns the relocations of its `.text` section.
+fn riscv64_tls_relocations(tls_model: &str) -> Vec<Riscv64Reloc> {
+    let (relocs, _) = build_riscv64_object(
+        &[("tls_model", tls_model)],
+        &[],
+        &[types::I64],
+        |module, bcx| {
+            let tls = module
+                .declare_data("tls_data", Linkage::Import, true, true)
+                .unwrap();
+            let tls = module.declare_data_in_func(tls, &mut bcx.func);
+            let addr = bcx.ins().tls_value(types::I64, tls);
+            bcx.ins().return_(&[addr]);
+        },
+    );
+    relocs
+}
+
+#[test]
+fn riscv64_tls_gd_relocations() {
+    let relocs = riscv64_tls_relocations("elf_gd");
+
+    // `auipc`+`addi` computing the address of the GOT entry, followed by the
+    // call to `__tls_get_addr`.
+    let gd = relocs
+        .iter()
+        .position(|r| r.r_type == object::elf::R_RISCV_TLS_GD_HI20)
+        .unwrap_or_else(|| panic!("no TLS GD relocation in {relocs:?}"));
+    let offset = relocs[gd].offset;
+    assert_eq!(relocs[gd].symbol, "tls_data");
+    assert_eq!(relocs[gd + 1].offset, offset + 4, "{relocs:?}");
+    assert_eq!(relocs[gd + 1].r_type, object::elf::R_RISCV_PCREL_LO12_I);
+    assert_eq!(relocs[gd + 2].offset, offset + 8, "{relocs:?}");
+    assert_eq!(relocs[gd + 2].r_type, object::elf::R_RISCV_CALL_PLT);
+    assert_eq!(relocs[gd + 2].symbol, "__tls_get_addr");
+}
+
+#[test]
+fn riscv64_tls_ie_relocations() {
+    let relocs = riscv64_tls_relocations("elf_ie");
+
+    // `auipc`+`ld` loading the thread pointer offset from the GOT.
+    let ie = relocs
+        .iter()
+        .position(|r| r.r_type == object::elf::R_RISCV_TLS_GOT_HI20)
+        .unwrap_or_else(|| panic!("no TLS IE relocation in {relocs:?}"));
+    let offset = relocs[ie].offset;
+    assert_eq!(relocs[ie].symbol, "tls_data");
+    assert_eq!(relocs[ie + 1].offset, offset + 4, "{relocs:?}");
+    assert_eq!(relocs[ie + 1].r_type, object::elf::R_RISCV_PCREL_LO12_I);
+    assert_eq!(relocs.len(), ie + 2, "{relocs:?}");
+}
+
+#[test]
+fn riscv64_tls_le_relocations() {
+    let relocs = riscv64_tls_relocations("elf_le");
+
+    // `lui`+`add tp`+`addi`, all of them referring to the symbol itself.
+    let le = relocs
+        .iter()
+        .position(|r| r.r_type == object::elf::R_RISCV_TPREL_HI20)
+        .unwrap_or_else(|| panic!("no TLS LE relocation in {relocs:?}"));
+    let offset = relocs[le].offset;
+    let expected = [
+        object::elf::R_RISCV_TPREL_HI20,
+        object::elf::R_RISCV_TPREL_ADD,
+        object::elf::R_RISCV_TPREL_LO12_I,
+    ];
+    for (i, r_type) in expected.into_iter().enumerate() {
+        let reloc = &relocs[le + i];
+        assert_eq!(reloc.offset, offset + 4 * i as u64, "{relocs:?}");
+        assert_eq!(reloc.r_type, r_type, "{relocs:?}");
+        assert_eq!(reloc.symbol, "tls_data");
+    }
+    assert!(
+        riscv64_reloc_targets(&relocs, object::elf::R_RISCV_CALL_PLT).is_empty(),
+        "{relocs:?}"
+    );
+}
@@ -1,7 +1,7 @@
 use std::{
     collections::{HashSet, hash_map::RandomState},
     sync::{
-        Arc, RwLock,
+        Arc, Barrier, RwLock,
         atomic::{AtomicBool, Ordering},
     },
 };
@@ -295,3 +295,137 @@ fn test_memory_size_accessibility() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_atomic_load_store_litmus() -> Result<()> {
+    const MESSAGES: u32 = 200;
+    const ROUNDS: usize = 2000;
+
+    // Message passing for each width of atomic stores and loads: `send`
+    // writes each message to a plain location before publishing its number
+    // with an atomic store, so `recv` must never observe an older message
+    // than the number that it loaded atomically.
+    let widths = [
+        ("i32", "i32.atomic.store8", "i32.atomic.load8_u"),
+        ("i32", "i32.atomic.store16", "i32.atomic.load16_u"),
+        ("i32", "i32.atomic.store", "i32.atomic.load"),
+        ("i64", "i64.atomic.store", "i64.atomic.load"),
+    ];
+    let mut wat = String::from(r#"(module (import "env" "memory" (memory 1 1 shared))"#);
+    for (i, (ty, store, load)) in widths.iter().enumerate() {
+        let flag = i * 16;
+        let data = flag + 8;
+        wat.push_str(&format!(
+            r#"
+            (func (export "send{i}") (param $n {ty})
+                (local $i {ty})
+                (loop $next
+                    (local.set $i ({ty}.add (local.get $i) ({ty}.const 1)))
+                    ({ty}.store (i32.const {data}) (local.get $i))
+                    ({store} (i32.const {flag}) (local.get $i))
+                    (br_if $next ({ty}.lt_u (local.get $i) (local.get $n)))))
+            (func (export "recv{i}") (param $n {ty}) (result i32)
+                (local $seen {ty})
+                (loop $next
+                    (local.set $seen ({load} (i32.const {flag})))
+                    (if ({ty}.lt_u ({ty}.load (i32.const {data})) (local.get $seen))
+                        (then (return (i32.const 0))))
+                    (br_if $next ({ty}.lt_u (local.get $seen) (local.get $n))))
+                (i32.const 1))
+            "#
+        ));
+    }
+
+    // Store buffering: with sequentially consistent stores and loads, at
+    // least one of the two threads has to observe the other's store.
+    wat.push_str(
+        r#"
+        (func (export "sb_a") (result i32)
+            (i32.atomic.store (i32.const 128) (i32.const 1))
+            (i32.atomic.load (i32.const 136)))
+        (func (export "sb_b") (result i32)
+            (i32.atomic.store (i32.const 136) (i32.const 1))
+            (i32.atomic.load (i32.const 128)))
+        (func (export "sb_reset")
+            (i32.atomic.store (i32.const 128) (i32.const 0))
+            (i32.atomic.store (i32.const 136) (i32.const 0)))
+        )"#,
+    );
+
+    let Some(engine) = engine() else {
+        return Ok(());
+    };
+    let module = Module::new(&engine, &wat)?;
+    let shared_memory = SharedMemory::new(&engine, MemoryType::shared(1, 1))?;
+    let barrier = Arc::new(Barrier::new(2));
+
+    let threads = ["a", "b"].map(|name| {
+        let engine = engine.clone();
+        let module = module.clone();
+        let shared_memory = shared_memory.clone();
+        let barrier = barrier.clone();
+        std::thread::spawn(move || {
+            let mut store = Store::new(&engine, ());
+            let instance = Instance::new(&mut store, &module, &[shared_memory.into()]).unwrap();
+
+            for (i, (ty, _, _)) in widths.iter().enumerate() {
+                barrier.wait();
+                if name == "a" {
+                    match *ty {
+                        "i32" => instance
+                            .get_typed_func::<u32, ()>(&mut store, &format!("send{i}"))
+                            .unwrap()
+                            .call(&mut store, MESSAGES)
+                            .unwrap(),
+                        _ => instance
+                            .get_typed_func::<u64, ()>(&mut store, &format!("send{i}"))
+                            .unwrap()
+                            .call(&mut store, MESSAGES.into())
+                            .unwrap(),
+                    }
+                } else {
+                    let ok = match *ty {
+                        "i32" => instance
+                            .get_typed_func::<u32, u32>(&mut store, &format!("recv{i}"))
+                            .unwrap()
+                            .call(&mut store, MESSAGES)
+                            .unwrap(),
+                        _ => instance
+                            .get_typed_func::<u64, u32>(&mut store, &format!("recv{i}"))
+                            .unwrap()
+                            .call(&mut store, MESSAGES.into())
+                            .unwrap(),
+                    };
+                    assert_eq!(ok, 1, "message passing with {:?}", widths[i]);
+                }
+            }
+
+            let sb = instance
+                .get_typed_func::<(), u32>(&mut store, &format!("sb_{name}"))
+                .unwrap();
+            let reset = instance
+                .get_typed_func::<(), ()>(&mut store, "sb_reset")
+                .unwrap();
+            let mut observed = Vec::with_capacity(ROUNDS);
+            for _ in 0..ROUNDS {
+                barrier.wait();
+                observed.push(sb.call(&mut store, ()).unwrap());
+                barrier.wait();
+                if name == "a" {
+                    reset.call(&mut store, ()).unwrap();
+                }
+            }
+            observed
+        })
+    });
+    let [a, b] = threads.map(|thread| thread.join().unwrap());
+    for (round, (a, b)) in a.iter().zip(&b).enumerate() {
+        assert!(
+            *a == 1 || *b == 1,
+            "both loads missed the stores in round {round}"
+        );
+    }
+
+    Ok(())
+}
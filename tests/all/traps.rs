@@ -696,6 +696,69 @@ fn trap_codes() {
     }
 }
 
+#[test]
+fn memory_access_trap_codes() {
+    // Every width and extension of loads and stores just past the end of the
+    // memory faults in the guard region, and the faulting access must be
+    // mapped back to an out-of-bounds trap.
+    let loads = [
+        "i32.load",
+        "i32.load8_s",
+        "i32.load8_u",
+        "i32.load16_s",
+        "i32.load16_u",
+        "i64.load",
+        "i64.load8_s",
+        "i64.load16_u",
+        "i64.load32_s",
+        "i64.load32_u",
+        "f32.load",
+        "f64.load",
+    ];
+    for op in loads {
+        println!("testing {op}");
+        assert_trap_code(
+            &format!(
+                r#"
+                    (module
+                      (memory 1)
+                      (func $start (drop ({op} (i32.const 65536))))
+                      (start $start)
+                    )
+                "#
+            ),
+            Trap::MemoryOutOfBounds,
+        );
+    }
+
+    let stores = [
+        ("i32.store", "i32.const 1"),
+        ("i32.store8", "i32.const 1"),
+        ("i32.store16", "i32.const 1"),
+        ("i64.store", "i64.const 1"),
+        ("i64.store8", "i64.const 1"),
+        ("i64.store16", "i64.const 1"),
+        ("i64.store32", "i64.const 1"),
+        ("f32.store", "f32.const 1"),
+        ("f64.store", "f64.const 1"),
+    ];
+    for (op, value) in stores {
+        println!("testing {op}");
+        assert_trap_code(
+            &format!(
+                r#"
+                    (module
+                      (memory 1)
+                      (func $start ({op} (i32.const 65536) ({value})))
+                      (start $start)
+                    )
+                "#
+            ),
+            Trap::MemoryOutOfBounds,
+        );
+    }
+}
+
 fn rustc(src: &str) -> Vec<u8> {
     let td = tempfile::TempDir::new().unwrap();
     let output = td.path().join("foo.wasm");
@@ -1149,6 +1212,75 @@ fn standalone_backtrace() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn host_backtrace_walks_frame_records() -> Result<()> {
+    // Calls into the host record the return address of the wasm caller from
+    // its trampoline's frame record, and the rest of the backtrace follows the
+    // saved frame pointers and return addresses from there. Capture from call
+    // sites at different depths, through frames of different shapes, and
+    // check that every frame resolves to the right call site.
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, Vec::new());
+    let module = Module::new(
+        &engine,
+        r#"
+            (module
+                (import "" "" (func $host))
+                (func $outer (export "f") (param i64) (result i64)
+                    call $host
+                    local.get 0
+                    call $middle
+                    call $host)
+                (func $middle (param i64) (result i64)
+                    (local i64 i64 i64)
+                    (local.set 1 (i64.mul (local.get 0) (i64.const 3)))
+                    (local.set 2 (i64.add (local.get 0) (i64.const 5)))
+                    (local.set 3 (i64.xor (local.get 1) (local.get 2)))
+                    (call $inner (local.get 3))
+                    (i64.add (local.get 1))
+                    (i64.add (local.get 2))
+                    (i64.add (local.get 3)))
+                (func $inner (param i64) (result i64)
+                    call $host
+                    (i64.sub (local.get 0) (i64.const 1)))
+            )
+        "#,
+    )?;
+    let func = Func::wrap(&mut store, |mut cx: Caller<'_, Vec<Vec<_>>>| {
+        let trace = WasmBacktrace::capture(&cx)
+            .frames()
+            .iter()
+            .map(|frame| (frame.func_name().unwrap().to_string(), frame.func_offset()))
+            .collect();
+        cx.data_mut().push(trace);
+    });
+    let instance = Instance::new(&mut store, &module, &[func.into()])?;
+    let f = instance.get_typed_func::<i64, i64>(&mut store, "f")?;
+    assert_eq!(f.call(&mut store, 7)?, 82);
+
+    let traces = store.data();
+    let names = traces
+        .iter()
+        .map(|trace| trace.iter().map(|(name, _)| name.as_str()).collect())
+        .collect::<Vec<Vec<_>>>();
+    assert_eq!(
+        names,
+        [
+            vec!["outer"],
+            vec!["inner", "middle", "outer"],
+            vec!["outer"]
+        ]
+    );
+
+    // The three frames of `outer` are at its three calls, in order.
+    let outer = traces
+        .iter()
+        .map(|trace| trace.last().unwrap().1.unwrap())
+        .collect::<Vec<_>>();
+    assert!(outer[0] < outer[1] && outer[1] < outer[2], "{outer:?}");
+    Ok(())
+}
+
 #[test]
 fn standalone_backtrace_disabled() -> Result<()> {
     let mut config = Config::new();
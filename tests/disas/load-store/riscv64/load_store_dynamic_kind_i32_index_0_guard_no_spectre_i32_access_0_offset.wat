@@ -23,35 +23,35 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a5, 0x40(a0)
-;;       slli    a2, a2, 0x20
-;;       srli    a4, a2, 0x20
-;;       addi    a5, a5, -4
-;;       bgeu    a5, a4, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a5, 0x38(a0)
-;;       add     a4, a5, a4
-;;       sw      a3, 0(a4)
+;;       ld      a6, 0x40(a0)
+;;       slli    t2, a2, 0x20
+;;       srli    a7, t2, 0x20
+;;       addi    a6, a6, -4
+;;       bltu    a6, a7, 0x20
+;;       ld      t3, 0x38(a0)
+;;       add     a7, t3, a7
+;;       sw      a3, 0(a7)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a3, 0x40(a0)
-;;       slli    a2, a2, 0x20
-;;       srli    a4, a2, 0x20
-;;       addi    a3, a3, -4
-;;       bgeu    a3, a4, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a5, 0x38(a0)
-;;       add     a4, a5, a4
-;;       lw      a0, 0(a4)
+;;       ld      a6, 0x40(a0)
+;;       slli    t2, a2, 0x20
+;;       srli    a7, t2, 0x20
+;;       addi    a6, a6, -4
+;;       bltu    a6, a7, 0x20
+;;       ld      t3, 0x38(a0)
+;;       add     a7, t3, a7
+;;       lw      a0, 0(a7)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
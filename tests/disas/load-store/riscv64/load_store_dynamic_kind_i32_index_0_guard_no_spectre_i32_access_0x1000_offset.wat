@@ -23,43 +23,43 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a5, 0x40(a0)
-;;       slli    a4, a2, 0x20
-;;       srli    a1, a4, 0x20
-;;       lui     a4, 1
-;;       addi    a2, a4, 4
-;;       sub     a5, a5, a2
-;;       bgeu    a5, a1, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
+;;       ld      t5, 0x40(a0)
+;;       slli    t4, a2, 0x20
+;;       srli    a1, t4, 0x20
+;;       lui     t4, 1
+;;       addi    a2, t4, 4
+;;       sub     t5, t5, a2
+;;       bltu    t5, a1, 0x28
 ;;       ld      a0, 0x38(a0)
 ;;       add     a0, a0, a1
-;;       lui     t6, 1
-;;       add     t6, t6, a0
-;;       sw      a3, 0(t6)
+;;       lui     t5, 1
+;;       add     a1, a0, t5
+;;       sw      a3, 0(a1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a5, 0x40(a0)
-;;       slli    a4, a2, 0x20
-;;       srli    a1, a4, 0x20
-;;       lui     a4, 1
-;;       addi    a2, a4, 4
-;;       sub     a5, a5, a2
-;;       bgeu    a5, a1, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
+;;       ld      t5, 0x40(a0)
+;;       slli    t4, a2, 0x20
+;;       srli    a1, t4, 0x20
+;;       lui     t4, 1
+;;       addi    a2, t4, 4
+;;       sub     t5, t5, a2
+;;       bltu    t5, a1, 0x28
 ;;       ld      a0, 0x38(a0)
 ;;       add     a0, a0, a1
-;;       lui     t6, 1
-;;       add     t6, t6, a0
-;;       lw      a0, 0(t6)
+;;       lui     t5, 1
+;;       add     a1, a0, t5
+;;       lw      a0, 0(a1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
@@ -23,51 +23,51 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       slli    a2, a2, 0x20
-;;       srli    a4, a2, 0x20
-;;       lui     a1, 0x3fffc
-;;       addi    a5, a1, 1
-;;       slli    a5, a5, 2
-;;       add     a5, a4, a5
-;;       bgeu    a5, a4, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a1, 0x40(a0)
-;;       bgeu    a1, a5, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a5, 0x38(a0)
-;;       add     a5, a5, a4
-;;       lui     a4, 0xffff
-;;       slli    a0, a4, 4
-;;       add     a5, a5, a0
-;;       sw      a3, 0(a5)
+;;       slli    a1, a2, 0x20
+;;       srli    a2, a1, 0x20
+;;       lui     t5, 0xffff
+;;       slli    a1, t5, 4
+;;       addi    a4, a1, 4
+;;       add     a1, a2, a4
+;;       bltu    a1, a2, 0x34
+;;       ld      a4, 0x40(a0)
+;;       bltu    a4, a1, 0x30
+;;       ld      a4, 0x38(a0)
+;;       add     a4, a4, a2
+;;       lui     a2, 0xffff
+;;       slli    a5, a2, 4
+;;       add     a4, a4, a5
+;;       sw      a3, 0(a4)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       slli    a2, a2, 0x20
-;;       srli    a4, a2, 0x20
-;;       lui     a1, 0x3fffc
-;;       addi    a3, a1, 1
-;;       slli    a5, a3, 2
-;;       add     a3, a4, a5
-;;       bgeu    a3, a4, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a5, 0x40(a0)
-;;       bgeu    a5, a3, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a5, 0x38(a0)
-;;       add     a5, a5, a4
-;;       lui     a4, 0xffff
-;;       slli    a0, a4, 4
-;;       add     a5, a5, a0
-;;       lw      a0, 0(a5)
+;;       slli    a1, a2, 0x20
+;;       srli    a2, a1, 0x20
+;;       lui     t5, 0xffff
+;;       slli    a1, t5, 4
+;;       addi    a3, a1, 4
+;;       add     a1, a2, a3
+;;       bltu    a1, a2, 0x34
+;;       ld      a3, 0x40(a0)
+;;       bltu    a3, a1, 0x30
+;;       ld      a3, 0x38(a0)
+;;       add     a3, a3, a2
+;;       lui     a2, 0xffff
+;;       slli    a4, a2, 4
+;;       add     a3, a3, a4
+;;       lw      a0, 0(a3)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
+;;       .byte   0x00, 0x00, 0x00, 0x00
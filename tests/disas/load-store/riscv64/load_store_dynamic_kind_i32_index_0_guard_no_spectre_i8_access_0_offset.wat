@@ -23,33 +23,33 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a5, 0x40(a0)
-;;       slli    a1, a2, 0x20
-;;       srli    a4, a1, 0x20
-;;       bltu    a4, a5, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a5, 0x38(a0)
-;;       add     a4, a5, a4
-;;       sb      a3, 0(a4)
+;;       ld      t2, 0x40(a0)
+;;       slli    t1, a2, 0x20
+;;       srli    a6, t1, 0x20
+;;       bgeu    a6, t2, 0x20
+;;       ld      a7, 0x38(a0)
+;;       add     a6, a7, a6
+;;       sb      a3, 0(a6)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a4, 0x40(a0)
-;;       slli    a1, a2, 0x20
-;;       srli    a3, a1, 0x20
-;;       bltu    a3, a4, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a4, 0x38(a0)
-;;       add     a3, a4, a3
-;;       lbu     a0, 0(a3)
+;;       ld      t2, 0x40(a0)
+;;       slli    t1, a2, 0x20
+;;       srli    a6, t1, 0x20
+;;       bgeu    a6, t2, 0x20
+;;       ld      a7, 0x38(a0)
+;;       add     a6, a7, a6
+;;       lbu     a0, 0(a6)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
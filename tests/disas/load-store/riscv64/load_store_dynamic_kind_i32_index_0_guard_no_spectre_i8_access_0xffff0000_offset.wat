@@ -25,14 +25,13 @@
 ;;       mv      s0, sp
 ;;       slli    a1, a2, 0x20
 ;;       srli    a2, a1, 0x20
-;;       auipc   a1, 0
-;;       ld      a1, 0x48(a1)
-;;       add     a1, a2, a1
-;;       bgeu    a1, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
+;;       lui     t5, 0xffff
+;;       slli    a1, t5, 4
+;;       addi    a4, a1, 1
+;;       add     a1, a2, a4
+;;       bltu    a1, a2, 0x34
 ;;       ld      a4, 0x40(a0)
-;;       bgeu    a4, a1, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
+;;       bltu    a4, a1, 0x30
 ;;       ld      a4, 0x38(a0)
 ;;       add     a4, a4, a2
 ;;       lui     a2, 0xffff
@@ -43,7 +42,7 @@
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
-;;       .byte   0x01, 0x00, 0xff, 0xff
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
@@ -53,14 +52,13 @@
 ;;       mv      s0, sp
 ;;       slli    a1, a2, 0x20
 ;;       srli    a2, a1, 0x20
-;;       auipc   a1, 0
-;;       ld      a1, 0x48(a1)
-;;       add     a1, a2, a1
-;;       bgeu    a1, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
+;;       lui     t5, 0xffff
+;;       slli    a1, t5, 4
+;;       addi    a3, a1, 1
+;;       add     a1, a2, a3
+;;       bltu    a1, a2, 0x34
 ;;       ld      a3, 0x40(a0)
-;;       bgeu    a3, a1, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
+;;       bltu    a3, a1, 0x30
 ;;       ld      a3, 0x38(a0)
 ;;       add     a3, a3, a2
 ;;       lui     a2, 0xffff
@@ -71,5 +69,5 @@
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
-;;       .byte   0x01, 0x00, 0xff, 0xff
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;       .byte   0x00, 0x00, 0x00, 0x00
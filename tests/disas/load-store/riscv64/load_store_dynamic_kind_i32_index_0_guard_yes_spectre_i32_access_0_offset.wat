@@ -23,17 +23,16 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a5, 0x40(a0)
-;;       ld      a1, 0x38(a0)
-;;       slli    a0, a2, 0x20
-;;       srli    a4, a0, 0x20
-;;       addi    a2, a5, -4
-;;       sltu    a2, a2, a4
-;;       add     a1, a1, a4
-;;       neg     a5, a2
-;;       not     a2, a5
-;;       and     a4, a1, a2
-;;       sw      a3, 0(a4)
+;;       ld      t4, 0x40(a0)
+;;       ld      t5, 0x38(a0)
+;;       slli    t3, a2, 0x20
+;;       srli    a0, t3, 0x20
+;;       addi    a1, t4, -4
+;;       add     t4, t5, a0
+;;       sltu    a7, a1, a0
+;;       addi    t5, a7, -1
+;;       and     a0, t4, t5
+;;       sw      a3, 0(a0)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -44,17 +43,16 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a4, 0x40(a0)
-;;       ld      a1, 0x38(a0)
-;;       slli    a0, a2, 0x20
-;;       srli    a3, a0, 0x20
-;;       addi    a2, a4, -4
-;;       sltu    a2, a2, a3
-;;       add     a1, a1, a3
-;;       neg     a5, a2
-;;       not     a2, a5
-;;       and     a3, a1, a2
-;;       lw      a0, 0(a3)
+;;       ld      t4, 0x40(a0)
+;;       ld      t5, 0x38(a0)
+;;       slli    t3, a2, 0x20
+;;       srli    a0, t3, 0x20
+;;       addi    a1, t4, -4
+;;       add     t4, t5, a0
+;;       sltu    a7, a1, a0
+;;       addi    t5, a7, -1
+;;       and     a0, t4, t5
+;;       lw      a0, 0(a0)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -23,55 +23,53 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       slli    a1, a2, 0x20
-;;       srli    a2, a1, 0x20
-;;       lui     a5, 0x3fffc
-;;       addi    a1, a5, 1
-;;       slli    a4, a1, 2
-;;       add     a1, a2, a4
-;;       bgeu    a1, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a4, 0x40(a0)
-;;       ld      a5, 0x38(a0)
-;;       sltu    a4, a4, a1
-;;       add     a2, a5, a2
-;;       lui     a1, 0xffff
-;;       slli    a5, a1, 4
-;;       add     a2, a2, a5
-;;       neg     a0, a4
-;;       not     a4, a0
-;;       and     a4, a2, a4
-;;       sw      a3, 0(a4)
+;;       slli    a4, a2, 0x20
+;;       srli    a5, a4, 0x20
+;;       lui     a2, 0xffff
+;;       slli    a4, a2, 4
+;;       addi    t0, a4, 4
+;;       add     a4, a5, t0
+;;       bltu    a4, a5, 0x3c
+;;       ld      t0, 0x40(a0)
+;;       ld      t1, 0x38(a0)
+;;       add     a5, t1, a5
+;;       lui     t1, 0xffff
+;;       slli    t1, t1, 4
+;;       add     a5, a5, t1
+;;       sltu    a4, t0, a4
+;;       addi    t0, a4, -1
+;;       and     t1, a5, t0
+;;       sw      a3, 0(t1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       slli    a1, a2, 0x20
-;;       srli    a2, a1, 0x20
-;;       lui     a5, 0x3fffc
-;;       addi    a1, a5, 1
-;;       slli    a3, a1, 2
-;;       add     a1, a2, a3
-;;       bgeu    a1, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a3, 0x40(a0)
-;;       ld      a4, 0x38(a0)
-;;       sltu    a3, a3, a1
-;;       add     a2, a4, a2
-;;       lui     a1, 0xffff
-;;       slli    a4, a1, 4
-;;       add     a2, a2, a4
-;;       neg     a0, a3
-;;       not     a3, a0
-;;       and     a4, a2, a3
-;;       lw      a0, 0(a4)
+;;       slli    a3, a2, 0x20
+;;       srli    a5, a3, 0x20
+;;       lui     a2, 0xffff
+;;       slli    a4, a2, 4
+;;       addi    t0, a4, 4
+;;       add     a4, a5, t0
+;;       bltu    a4, a5, 0x3c
+;;       ld      t0, 0x40(a0)
+;;       ld      t1, 0x38(a0)
+;;       add     a5, t1, a5
+;;       lui     t1, 0xffff
+;;       slli    t1, t1, 4
+;;       add     a5, a5, t1
+;;       sltu    a3, t0, a4
+;;       addi    t0, a3, -1
+;;       and     t1, a5, t0
+;;       lw      a0, 0(t1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
@@ -23,21 +23,20 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a4, 0x40(a0)
+;;       ld      t0, 0x40(a0)
 ;;       ld      a5, 0x38(a0)
-;;       slli    a0, a2, 0x20
-;;       srli    a1, a0, 0x20
-;;       lui     a0, 1
-;;       addi    a0, a0, 1
-;;       sub     a0, a4, a0
-;;       sltu    a0, a0, a1
-;;       add     a5, a5, a1
+;;       slli    a1, a2, 0x20
+;;       srli    a4, a1, 0x20
+;;       lui     a1, 1
+;;       addi    t1, a1, 1
+;;       sub     a2, t0, t1
+;;       add     a5, a5, a4
 ;;       lui     a1, 1
 ;;       add     a5, a5, a1
-;;       neg     a4, a0
-;;       not     a0, a4
-;;       and     a1, a5, a0
-;;       sb      a3, 0(a1)
+;;       sltu    a0, a2, a4
+;;       addi    a2, a0, -1
+;;       and     a4, a5, a2
+;;       sb      a3, 0(a4)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -48,21 +47,20 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a3, 0x40(a0)
-;;       ld      a5, 0x38(a0)
-;;       slli    a4, a2, 0x20
-;;       srli    a1, a4, 0x20
-;;       lui     a4, 1
-;;       addi    a0, a4, 1
-;;       sub     a0, a3, a0
-;;       sltu    a0, a0, a1
-;;       add     a5, a5, a1
+;;       ld      a5, 0x40(a0)
+;;       ld      a4, 0x38(a0)
+;;       slli    a1, a2, 0x20
+;;       srli    a3, a1, 0x20
 ;;       lui     a1, 1
-;;       add     a5, a5, a1
-;;       neg     a3, a0
-;;       not     a0, a3
-;;       and     a1, a5, a0
-;;       lbu     a0, 0(a1)
+;;       addi    t0, a1, 1
+;;       sub     a2, a5, t0
+;;       add     a4, a4, a3
+;;       lui     a1, 1
+;;       add     a4, a4, a1
+;;       sltu    a0, a2, a3
+;;       addi    a2, a0, -1
+;;       and     a4, a4, a2
+;;       lbu     a0, 0(a4)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
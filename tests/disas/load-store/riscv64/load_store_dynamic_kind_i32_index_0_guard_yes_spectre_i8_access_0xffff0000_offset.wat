@@ -23,31 +23,27 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       mv      a1, a0
 ;;       slli    a4, a2, 0x20
-;;       srli    a0, a4, 0x20
-;;       auipc   a5, 0
-;;       ld      a5, 0x54(a5)
-;;       add     a5, a0, a5
-;;       bgeu    a5, a0, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       mv      a2, a1
-;;       ld      a1, 0x40(a2)
-;;       ld      a2, 0x38(a2)
-;;       sltu    a1, a1, a5
-;;       add     a0, a2, a0
-;;       lui     a5, 0xffff
-;;       slli    a2, a5, 4
-;;       add     a0, a0, a2
-;;       neg     a4, a1
-;;       not     a1, a4
-;;       and     a2, a0, a1
-;;       sb      a3, 0(a2)
+;;       srli    a5, a4, 0x20
+;;       lui     a2, 0xffff
+;;       slli    a4, a2, 4
+;;       addi    t0, a4, 1
+;;       add     a4, a5, t0
+;;       bltu    a4, a5, 0x3c
+;;       ld      t0, 0x40(a0)
+;;       ld      t1, 0x38(a0)
+;;       add     a5, t1, a5
+;;       lui     t1, 0xffff
+;;       slli    t1, t1, 4
+;;       add     a5, a5, t1
+;;       sltu    a4, t0, a4
+;;       addi    t0, a4, -1
+;;       and     t1, a5, t0
+;;       sb      a3, 0(t1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
-;;       .byte   0x01, 0x00, 0xff, 0xff
 ;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
@@ -55,29 +51,25 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       mv      a1, a0
-;;       slli    a4, a2, 0x20
-;;       srli    a0, a4, 0x20
-;;       auipc   a5, 0
-;;       ld      a5, 0x54(a5)
-;;       add     a5, a0, a5
-;;       bgeu    a5, a0, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       mv      a2, a1
-;;       ld      a1, 0x40(a2)
-;;       ld      a2, 0x38(a2)
-;;       sltu    a1, a1, a5
-;;       add     a0, a2, a0
-;;       lui     a5, 0xffff
-;;       slli    a2, a5, 4
-;;       add     a0, a0, a2
-;;       neg     a4, a1
-;;       not     a1, a4
-;;       and     a2, a0, a1
-;;       lbu     a0, 0(a2)
+;;       slli    a3, a2, 0x20
+;;       srli    a5, a3, 0x20
+;;       lui     a2, 0xffff
+;;       slli    a4, a2, 4
+;;       addi    t0, a4, 1
+;;       add     a4, a5, t0
+;;       bltu    a4, a5, 0x3c
+;;       ld      t0, 0x40(a0)
+;;       ld      t1, 0x38(a0)
+;;       add     a5, t1, a5
+;;       lui     t1, 0xffff
+;;       slli    t1, t1, 4
+;;       add     a5, a5, t1
+;;       sltu    a3, t0, a4
+;;       addi    t0, a3, -1
+;;       and     t1, a5, t0
+;;       lbu     a0, 0(t1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
-;;       .byte   0x01, 0x00, 0xff, 0xff
 ;;       .byte   0x00, 0x00, 0x00, 0x00
@@ -23,37 +23,37 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a5, 0x40(a0)
-;;       slli    a1, a2, 0x20
-;;       srli    a4, a1, 0x20
-;;       bgeu    a5, a4, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a5, 0x38(a0)
-;;       add     a4, a5, a4
-;;       lui     t6, 1
-;;       add     t6, t6, a4
-;;       sw      a3, 0(t6)
+;;       ld      a7, 0x40(a0)
+;;       slli    a6, a2, 0x20
+;;       srli    t3, a6, 0x20
+;;       bltu    a7, t3, 0x28
+;;       ld      t4, 0x38(a0)
+;;       add     t3, t4, t3
+;;       lui     a7, 1
+;;       add     t4, t3, a7
+;;       sw      a3, 0(t4)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a4, 0x40(a0)
-;;       slli    a1, a2, 0x20
-;;       srli    a3, a1, 0x20
-;;       bgeu    a4, a3, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a4, 0x38(a0)
-;;       add     a3, a4, a3
-;;       lui     t6, 1
-;;       add     t6, t6, a3
-;;       lw      a0, 0(t6)
+;;       ld      a7, 0x40(a0)
+;;       slli    a6, a2, 0x20
+;;       srli    t3, a6, 0x20
+;;       bltu    a7, t3, 0x28
+;;       ld      t4, 0x38(a0)
+;;       add     t3, t4, t3
+;;       lui     a7, 1
+;;       add     t4, t3, a7
+;;       lw      a0, 0(t4)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
@@ -23,39 +23,39 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a5, 0x40(a0)
-;;       slli    a4, a2, 0x20
-;;       srli    a1, a4, 0x20
-;;       bgeu    a5, a1, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a0, 0x38(a0)
-;;       add     a0, a0, a1
-;;       lui     a5, 0xffff
-;;       slli    a1, a5, 4
-;;       add     a0, a0, a1
-;;       sw      a3, 0(a0)
+;;       ld      t3, 0x40(a0)
+;;       slli    a7, a2, 0x20
+;;       srli    t4, a7, 0x20
+;;       bltu    t3, t4, 0x2c
+;;       ld      t5, 0x38(a0)
+;;       add     t4, t5, t4
+;;       lui     t3, 0xffff
+;;       slli    t5, t3, 4
+;;       add     t4, t4, t5
+;;       sw      a3, 0(t4)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a5, 0x40(a0)
-;;       slli    a4, a2, 0x20
-;;       srli    a1, a4, 0x20
-;;       bgeu    a5, a1, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a0, 0x38(a0)
-;;       add     a0, a0, a1
-;;       lui     a5, 0xffff
-;;       slli    a1, a5, 4
-;;       add     a0, a0, a1
-;;       lw      a0, 0(a0)
+;;       ld      t3, 0x40(a0)
+;;       slli    a7, a2, 0x20
+;;       srli    t4, a7, 0x20
+;;       bltu    t3, t4, 0x2c
+;;       ld      t5, 0x38(a0)
+;;       add     t4, t5, t4
+;;       lui     t3, 0xffff
+;;       slli    t5, t3, 4
+;;       add     t4, t4, t5
+;;       lw      a0, 0(t4)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
@@ -23,16 +23,15 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a4, 0x40(a0)
-;;       ld      a1, 0x38(a0)
-;;       slli    a5, a2, 0x20
-;;       srli    a2, a5, 0x20
-;;       sltu    a4, a4, a2
-;;       add     a0, a1, a2
-;;       neg     a4, a4
-;;       not     a1, a4
-;;       and     a2, a0, a1
-;;       sw      a3, 0(a2)
+;;       ld      t3, 0x40(a0)
+;;       ld      t4, 0x38(a0)
+;;       slli    a7, a2, 0x20
+;;       srli    t5, a7, 0x20
+;;       add     t4, t4, t5
+;;       sltu    a6, t3, t5
+;;       addi    t3, a6, -1
+;;       and     t5, t4, t3
+;;       sw      a3, 0(t5)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -43,16 +42,15 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a3, 0x40(a0)
-;;       ld      a1, 0x38(a0)
-;;       slli    a5, a2, 0x20
-;;       srli    a2, a5, 0x20
-;;       sltu    a3, a3, a2
-;;       add     a0, a1, a2
-;;       neg     a4, a3
-;;       not     a1, a4
-;;       and     a2, a0, a1
-;;       lw      a0, 0(a2)
+;;       ld      t3, 0x40(a0)
+;;       ld      t4, 0x38(a0)
+;;       slli    a7, a2, 0x20
+;;       srli    t5, a7, 0x20
+;;       add     t4, t4, t5
+;;       sltu    a6, t3, t5
+;;       addi    t3, a6, -1
+;;       and     t5, t4, t3
+;;       lw      a0, 0(t5)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -23,17 +23,15 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a5, 0x40(a0)
-;;       ld      a1, 0x38(a0)
-;;       slli    a0, a2, 0x20
-;;       srli    a4, a0, 0x20
-;;       sltu    a0, a4, a5
-;;       xori    a2, a0, 1
-;;       add     a1, a1, a4
-;;       neg     a5, a2
-;;       not     a2, a5
-;;       and     a4, a1, a2
-;;       sb      a3, 0(a4)
+;;       ld      t3, 0x40(a0)
+;;       ld      t4, 0x38(a0)
+;;       slli    a7, a2, 0x20
+;;       srli    t5, a7, 0x20
+;;       add     t4, t4, t5
+;;       sltu    a6, t5, t3
+;;       neg     t3, a6
+;;       and     t5, t4, t3
+;;       sb      a3, 0(t5)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -44,17 +42,15 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a4, 0x40(a0)
-;;       ld      a1, 0x38(a0)
-;;       slli    a0, a2, 0x20
-;;       srli    a3, a0, 0x20
-;;       sltu    a0, a3, a4
-;;       xori    a2, a0, 1
-;;       add     a1, a1, a3
-;;       neg     a5, a2
-;;       not     a2, a5
-;;       and     a3, a1, a2
-;;       lbu     a0, 0(a3)
+;;       ld      t3, 0x40(a0)
+;;       ld      t4, 0x38(a0)
+;;       slli    a7, a2, 0x20
+;;       srli    t5, a7, 0x20
+;;       add     t4, t4, t5
+;;       sltu    a6, t5, t3
+;;       neg     t3, a6
+;;       and     t5, t4, t3
+;;       lbu     a0, 0(t5)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -23,18 +23,17 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a5, 0x40(a0)
-;;       ld      a4, 0x38(a0)
-;;       slli    a1, a2, 0x20
-;;       srli    a0, a1, 0x20
-;;       sltu    a2, a5, a0
-;;       add     a4, a4, a0
-;;       lui     a5, 1
-;;       add     a4, a4, a5
-;;       neg     a0, a2
-;;       not     a2, a0
-;;       and     a4, a4, a2
-;;       sb      a3, 0(a4)
+;;       ld      t5, 0x40(a0)
+;;       ld      a1, 0x38(a0)
+;;       slli    t4, a2, 0x20
+;;       srli    a0, t4, 0x20
+;;       add     a1, a1, a0
+;;       lui     t4, 1
+;;       add     a1, a1, t4
+;;       sltu    t3, t5, a0
+;;       addi    t5, t3, -1
+;;       and     a1, a1, t5
+;;       sb      a3, 0(a1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -45,18 +44,17 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a4, 0x40(a0)
-;;       ld      a3, 0x38(a0)
-;;       slli    a1, a2, 0x20
-;;       srli    a5, a1, 0x20
-;;       sltu    a2, a4, a5
-;;       add     a3, a3, a5
-;;       lui     a4, 1
-;;       add     a3, a3, a4
-;;       neg     a0, a2
-;;       not     a2, a0
-;;       and     a4, a3, a2
-;;       lbu     a0, 0(a4)
+;;       ld      t5, 0x40(a0)
+;;       ld      a1, 0x38(a0)
+;;       slli    t4, a2, 0x20
+;;       srli    a0, t4, 0x20
+;;       add     a1, a1, a0
+;;       lui     t4, 1
+;;       add     a1, a1, t4
+;;       sltu    t3, t5, a0
+;;       addi    t5, t3, -1
+;;       and     a1, a1, t5
+;;       lbu     a0, 0(a1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -23,19 +23,19 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
+;;       mv      a5, a2
 ;;       ld      a4, 0x40(a0)
-;;       ld      a5, 0x38(a0)
-;;       slli    a2, a2, 0x20
-;;       srli    a0, a2, 0x20
-;;       sltu    a4, a4, a0
-;;       add     a5, a5, a0
-;;       lui     a2, 0xffff
-;;       slli    a0, a2, 4
-;;       add     a5, a5, a0
-;;       neg     a1, a4
-;;       not     a4, a1
-;;       and     a5, a5, a4
-;;       sb      a3, 0(a5)
+;;       ld      a2, 0x38(a0)
+;;       slli    t5, a5, 0x20
+;;       srli    a1, t5, 0x20
+;;       add     a2, a2, a1
+;;       lui     t5, 0xffff
+;;       slli    a5, t5, 4
+;;       add     a2, a2, a5
+;;       sltu    t4, a4, a1
+;;       addi    a0, t4, -1
+;;       and     a2, a2, a0
+;;       sb      a3, 0(a2)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -46,19 +46,19 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
+;;       mv      a4, a2
 ;;       ld      a3, 0x40(a0)
-;;       ld      a4, 0x38(a0)
-;;       slli    a2, a2, 0x20
-;;       srli    a5, a2, 0x20
-;;       sltu    a3, a3, a5
-;;       add     a4, a4, a5
-;;       lui     a2, 0xffff
-;;       slli    a5, a2, 4
-;;       add     a4, a4, a5
-;;       neg     a1, a3
-;;       not     a3, a1
-;;       and     a5, a4, a3
-;;       lbu     a0, 0(a5)
+;;       ld      a2, 0x38(a0)
+;;       slli    t5, a4, 0x20
+;;       srli    a1, t5, 0x20
+;;       add     a2, a2, a1
+;;       lui     t5, 0xffff
+;;       slli    a4, t5, 4
+;;       add     a2, a2, a4
+;;       sltu    t4, a3, a1
+;;       addi    a0, t4, -1
+;;       and     a2, a2, a0
+;;       lbu     a0, 0(a2)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
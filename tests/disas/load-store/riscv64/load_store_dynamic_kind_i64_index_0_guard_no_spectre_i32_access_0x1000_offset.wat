@@ -23,39 +23,39 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a4, 0x40(a0)
-;;       lui     a5, 1
-;;       addi    a5, a5, 4
-;;       sub     a4, a4, a5
-;;       bgeu    a4, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a4, 0x38(a0)
-;;       add     a4, a4, a2
-;;       lui     t6, 1
-;;       add     t6, t6, a4
-;;       sw      a3, 0(t6)
+;;       ld      t3, 0x40(a0)
+;;       lui     a7, 1
+;;       addi    t4, a7, 4
+;;       sub     t3, t3, t4
+;;       bltu    t3, a2, 0x28
+;;       ld      t4, 0x38(a0)
+;;       add     t4, t4, a2
+;;       lui     t3, 1
+;;       add     t5, t4, t3
+;;       sw      a3, 0(t5)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a3, 0x40(a0)
-;;       lui     a4, 1
-;;       addi    a4, a4, 4
-;;       sub     a3, a3, a4
-;;       bgeu    a3, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a4, 0x38(a0)
-;;       add     a4, a4, a2
-;;       lui     t6, 1
-;;       add     t6, t6, a4
-;;       lw      a0, 0(t6)
+;;       ld      t3, 0x40(a0)
+;;       lui     a7, 1
+;;       addi    t4, a7, 4
+;;       sub     t3, t3, t4
+;;       bltu    t3, a2, 0x28
+;;       ld      t4, 0x38(a0)
+;;       add     t4, t4, a2
+;;       lui     t3, 1
+;;       add     t5, t4, t3
+;;       lw      a0, 0(t5)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
@@ -23,47 +23,47 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       lui     a5, 0x3fffc
-;;       addi    a1, a5, 1
-;;       slli    a4, a1, 2
-;;       add     a1, a2, a4
-;;       bgeu    a1, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a4, 0x40(a0)
-;;       bgeu    a4, a1, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a4, 0x38(a0)
-;;       add     a4, a4, a2
-;;       lui     a2, 0xffff
-;;       slli    a5, a2, 4
-;;       add     a4, a4, a5
-;;       sw      a3, 0(a4)
+;;       lui     t3, 0xffff
+;;       slli    t5, t3, 4
+;;       addi    a1, t5, 4
+;;       add     t5, a2, a1
+;;       bltu    t5, a2, 0x34
+;;       ld      a1, 0x40(a0)
+;;       bltu    a1, t5, 0x30
+;;       ld      a1, 0x38(a0)
+;;       add     a1, a1, a2
+;;       lui     a0, 0xffff
+;;       slli    a2, a0, 4
+;;       add     a1, a1, a2
+;;       sw      a3, 0(a1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       lui     a5, 0x3fffc
-;;       addi    a1, a5, 1
-;;       slli    a3, a1, 2
-;;       add     a1, a2, a3
-;;       bgeu    a1, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a3, 0x40(a0)
-;;       bgeu    a3, a1, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a3, 0x38(a0)
-;;       add     a3, a3, a2
-;;       lui     a2, 0xffff
-;;       slli    a4, a2, 4
-;;       add     a3, a3, a4
-;;       lw      a0, 0(a3)
+;;       lui     t3, 0xffff
+;;       slli    t5, t3, 4
+;;       addi    a1, t5, 4
+;;       add     t5, a2, a1
+;;       bltu    t5, a2, 0x34
+;;       ld      a1, 0x40(a0)
+;;       bltu    a1, t5, 0x30
+;;       ld      a1, 0x38(a0)
+;;       add     a1, a1, a2
+;;       lui     a0, 0xffff
+;;       slli    a2, a0, 4
+;;       add     a1, a1, a2
+;;       lw      a0, 0(a1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
+;;       .byte   0x00, 0x00, 0x00, 0x00
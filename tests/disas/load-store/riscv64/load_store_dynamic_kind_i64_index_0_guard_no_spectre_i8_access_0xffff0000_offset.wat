@@ -23,14 +23,13 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       auipc   a5, 0
-;;       ld      a5, 0x48(a5)
-;;       add     a5, a2, a5
-;;       bgeu    a5, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
+;;       lui     t3, 0xffff
+;;       slli    t5, t3, 4
+;;       addi    a1, t5, 1
+;;       add     t5, a2, a1
+;;       bltu    t5, a2, 0x34
 ;;       ld      a1, 0x40(a0)
-;;       bgeu    a1, a5, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
+;;       bltu    a1, t5, 0x30
 ;;       ld      a1, 0x38(a0)
 ;;       add     a1, a1, a2
 ;;       lui     a0, 0xffff
@@ -41,7 +40,7 @@
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
-;;       .byte   0x01, 0x00, 0xff, 0xff
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
@@ -49,14 +48,13 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       auipc   a5, 0
-;;       ld      a5, 0x48(a5)
-;;       add     a5, a2, a5
-;;       bgeu    a5, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
+;;       lui     t3, 0xffff
+;;       slli    t5, t3, 4
+;;       addi    a1, t5, 1
+;;       add     t5, a2, a1
+;;       bltu    t5, a2, 0x34
 ;;       ld      a1, 0x40(a0)
-;;       bgeu    a1, a5, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
+;;       bltu    a1, t5, 0x30
 ;;       ld      a1, 0x38(a0)
 ;;       add     a1, a1, a2
 ;;       lui     a0, 0xffff
@@ -67,5 +65,5 @@
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
-;;       .byte   0x01, 0x00, 0xff, 0xff
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;       .byte   0x00, 0x00, 0x00, 0x00
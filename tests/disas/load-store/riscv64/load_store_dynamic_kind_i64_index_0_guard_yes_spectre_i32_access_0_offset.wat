@@ -23,15 +23,14 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a1, 0x40(a0)
-;;       ld      a5, 0x38(a0)
-;;       addi    a0, a1, -4
-;;       sltu    a0, a0, a2
-;;       add     a5, a5, a2
-;;       neg     a4, a0
-;;       not     a0, a4
-;;       and     a1, a5, a0
-;;       sw      a3, 0(a1)
+;;       ld      a7, 0x40(a0)
+;;       ld      t3, 0x38(a0)
+;;       addi    t4, a7, -4
+;;       add     a7, t3, a2
+;;       sltu    t2, t4, a2
+;;       addi    t3, t2, -1
+;;       and     t4, a7, t3
+;;       sw      a3, 0(t4)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -42,15 +41,14 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a1, 0x40(a0)
-;;       ld      a5, 0x38(a0)
-;;       addi    a0, a1, -4
-;;       sltu    a0, a0, a2
-;;       add     a5, a5, a2
-;;       neg     a3, a0
-;;       not     a0, a3
-;;       and     a1, a5, a0
-;;       lw      a0, 0(a1)
+;;       ld      a7, 0x40(a0)
+;;       ld      t3, 0x38(a0)
+;;       addi    t4, a7, -4
+;;       add     a7, t3, a2
+;;       sltu    t2, t4, a2
+;;       addi    t3, t2, -1
+;;       and     t4, a7, t3
+;;       lw      a0, 0(t4)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
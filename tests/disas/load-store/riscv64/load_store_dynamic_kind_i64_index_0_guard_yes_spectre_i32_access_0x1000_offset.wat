@@ -23,19 +23,18 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a5, 0x40(a0)
-;;       ld      a4, 0x38(a0)
-;;       lui     a0, 1
-;;       addi    a0, a0, 4
-;;       sub     a5, a5, a0
-;;       sltu    a5, a5, a2
-;;       add     a4, a4, a2
-;;       lui     a0, 1
-;;       add     a4, a4, a0
-;;       neg     a1, a5
-;;       not     a5, a1
-;;       and     a5, a4, a5
-;;       sw      a3, 0(a5)
+;;       ld      a4, 0x40(a0)
+;;       ld      a1, 0x38(a0)
+;;       lui     t5, 1
+;;       addi    a5, t5, 4
+;;       sub     a0, a4, a5
+;;       add     a1, a1, a2
+;;       lui     t5, 1
+;;       add     a1, a1, t5
+;;       sltu    t4, a0, a2
+;;       addi    a0, t4, -1
+;;       and     a2, a1, a0
+;;       sw      a3, 0(a2)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -46,19 +45,18 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a4, 0x40(a0)
-;;       ld      a3, 0x38(a0)
-;;       lui     a5, 1
-;;       addi    a5, a5, 4
-;;       sub     a4, a4, a5
-;;       sltu    a4, a4, a2
-;;       add     a3, a3, a2
-;;       lui     a5, 1
-;;       add     a3, a3, a5
-;;       neg     a1, a4
-;;       not     a4, a1
-;;       and     a5, a3, a4
-;;       lw      a0, 0(a5)
+;;       ld      a3, 0x40(a0)
+;;       ld      a1, 0x38(a0)
+;;       lui     t5, 1
+;;       addi    a4, t5, 4
+;;       sub     a0, a3, a4
+;;       add     a1, a1, a2
+;;       lui     t5, 1
+;;       add     a1, a1, t5
+;;       sltu    t4, a0, a2
+;;       addi    a0, t4, -1
+;;       and     a2, a1, a0
+;;       lw      a0, 0(a2)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
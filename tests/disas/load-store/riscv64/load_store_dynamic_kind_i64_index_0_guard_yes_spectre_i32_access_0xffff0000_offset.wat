@@ -23,51 +23,49 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       lui     a4, 0x3fffc
-;;       addi    a5, a4, 1
-;;       slli    a1, a5, 2
-;;       add     a5, a2, a1
-;;       bgeu    a5, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a1, 0x40(a0)
-;;       ld      a4, 0x38(a0)
-;;       sltu    a0, a1, a5
-;;       add     a1, a4, a2
-;;       lui     a5, 0xffff
-;;       slli    a2, a5, 4
-;;       add     a1, a1, a2
-;;       neg     a4, a0
-;;       not     a0, a4
-;;       and     a2, a1, a0
-;;       sw      a3, 0(a2)
+;;       lui     a1, 0xffff
+;;       slli    a4, a1, 4
+;;       addi    a4, a4, 4
+;;       add     a4, a2, a4
+;;       bltu    a4, a2, 0x3c
+;;       ld      a5, 0x40(a0)
+;;       ld      t0, 0x38(a0)
+;;       add     t0, t0, a2
+;;       lui     a2, 0xffff
+;;       slli    t1, a2, 4
+;;       add     t0, t0, t1
+;;       sltu    a1, a5, a4
+;;       addi    a4, a1, -1
+;;       and     a5, t0, a4
+;;       sw      a3, 0(a5)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       lui     a3, 0x3fffc
-;;       addi    a5, a3, 1
-;;       slli    a1, a5, 2
-;;       add     a5, a2, a1
-;;       bgeu    a5, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a1, 0x40(a0)
-;;       ld      a3, 0x38(a0)
-;;       sltu    a0, a1, a5
-;;       add     a1, a3, a2
-;;       lui     a5, 0xffff
-;;       slli    a2, a5, 4
-;;       add     a1, a1, a2
-;;       neg     a4, a0
-;;       not     a0, a4
-;;       and     a2, a1, a0
-;;       lw      a0, 0(a2)
+;;       lui     a1, 0xffff
+;;       slli    a3, a1, 4
+;;       addi    a4, a3, 4
+;;       add     a3, a2, a4
+;;       bltu    a3, a2, 0x3c
+;;       ld      a4, 0x40(a0)
+;;       ld      a5, 0x38(a0)
+;;       add     a5, a5, a2
+;;       lui     a2, 0xffff
+;;       slli    t0, a2, 4
+;;       add     a5, a5, t0
+;;       sltu    a1, a4, a3
+;;       addi    a3, a1, -1
+;;       and     a5, a5, a3
+;;       lw      a0, 0(a5)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
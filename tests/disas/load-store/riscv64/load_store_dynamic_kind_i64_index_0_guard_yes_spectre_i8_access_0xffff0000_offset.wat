@@ -23,27 +23,25 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       auipc   a4, 0
-;;       ld      a4, 0x50(a4)
+;;       lui     a1, 0xffff
+;;       slli    a4, a1, 4
+;;       addi    a4, a4, 1
 ;;       add     a4, a2, a4
-;;       bgeu    a4, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
+;;       bltu    a4, a2, 0x3c
 ;;       ld      a5, 0x40(a0)
-;;       ld      a0, 0x38(a0)
-;;       sltu    a4, a5, a4
-;;       add     a5, a0, a2
-;;       lui     a0, 0xffff
-;;       slli    a0, a0, 4
-;;       add     a5, a5, a0
-;;       neg     a2, a4
-;;       not     a4, a2
-;;       and     a0, a5, a4
-;;       sb      a3, 0(a0)
+;;       ld      t0, 0x38(a0)
+;;       add     t0, t0, a2
+;;       lui     a2, 0xffff
+;;       slli    t1, a2, 4
+;;       add     t0, t0, t1
+;;       sltu    a1, a5, a4
+;;       addi    a4, a1, -1
+;;       and     a5, t0, a4
+;;       sb      a3, 0(a5)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
-;;       .byte   0x01, 0x00, 0xff, 0xff
 ;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
@@ -51,25 +49,23 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       auipc   a3, 0
-;;       ld      a3, 0x50(a3)
-;;       add     a3, a2, a3
-;;       bgeu    a3, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
+;;       lui     a1, 0xffff
+;;       slli    a3, a1, 4
+;;       addi    a4, a3, 1
+;;       add     a3, a2, a4
+;;       bltu    a3, a2, 0x3c
 ;;       ld      a4, 0x40(a0)
 ;;       ld      a5, 0x38(a0)
-;;       sltu    a4, a4, a3
 ;;       add     a5, a5, a2
-;;       lui     a3, 0xffff
-;;       slli    a0, a3, 4
-;;       add     a5, a5, a0
-;;       neg     a2, a4
-;;       not     a4, a2
-;;       and     a0, a5, a4
-;;       lbu     a0, 0(a0)
+;;       lui     a2, 0xffff
+;;       slli    t0, a2, 4
+;;       add     a5, a5, t0
+;;       sltu    a1, a4, a3
+;;       addi    a3, a1, -1
+;;       and     a5, a5, a3
+;;       lbu     a0, 0(a5)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
-;;       .byte   0x01, 0x00, 0xff, 0xff
 ;;       .byte   0x00, 0x00, 0x00, 0x00
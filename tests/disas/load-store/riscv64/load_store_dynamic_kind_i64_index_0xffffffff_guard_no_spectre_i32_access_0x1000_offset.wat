@@ -23,33 +23,33 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a1, 0x40(a0)
-;;       bgeu    a1, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a1, 0x38(a0)
-;;       add     a1, a1, a2
-;;       lui     t6, 1
-;;       add     t6, t6, a1
-;;       sw      a3, 0(t6)
+;;       ld      t2, 0x40(a0)
+;;       bltu    t2, a2, 0x28
+;;       ld      a6, 0x38(a0)
+;;       add     a6, a6, a2
+;;       lui     t2, 1
+;;       add     a7, a6, t2
+;;       sw      a3, 0(a7)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a1, 0x40(a0)
-;;       bgeu    a1, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a1, 0x38(a0)
-;;       add     a1, a1, a2
-;;       lui     t6, 1
-;;       add     t6, t6, a1
-;;       lw      a0, 0(t6)
+;;       ld      t2, 0x40(a0)
+;;       bltu    t2, a2, 0x28
+;;       ld      a6, 0x38(a0)
+;;       add     a6, a6, a2
+;;       lui     t2, 1
+;;       add     a7, a6, t2
+;;       lw      a0, 0(a7)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
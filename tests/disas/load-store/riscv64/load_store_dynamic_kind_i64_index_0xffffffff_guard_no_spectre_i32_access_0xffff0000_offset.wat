@@ -23,35 +23,35 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a4, 0x40(a0)
-;;       bgeu    a4, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a4, 0x38(a0)
-;;       add     a4, a4, a2
-;;       lui     a5, 0xffff
-;;       slli    a5, a5, 4
-;;       add     a4, a4, a5
-;;       sw      a3, 0(a4)
+;;       ld      a6, 0x40(a0)
+;;       bltu    a6, a2, 0x2c
+;;       ld      a7, 0x38(a0)
+;;       add     a7, a7, a2
+;;       lui     a6, 0xffff
+;;       slli    t3, a6, 4
+;;       add     a7, a7, t3
+;;       sw      a3, 0(a7)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a3, 0x40(a0)
-;;       bgeu    a3, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a4, 0x38(a0)
-;;       add     a4, a4, a2
-;;       lui     a3, 0xffff
-;;       slli    a5, a3, 4
-;;       add     a4, a4, a5
-;;       lw      a0, 0(a4)
+;;       ld      a6, 0x40(a0)
+;;       bltu    a6, a2, 0x2c
+;;       ld      a7, 0x38(a0)
+;;       add     a7, a7, a2
+;;       lui     a6, 0xffff
+;;       slli    t3, a6, 4
+;;       add     a7, a7, t3
+;;       lw      a0, 0(a7)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
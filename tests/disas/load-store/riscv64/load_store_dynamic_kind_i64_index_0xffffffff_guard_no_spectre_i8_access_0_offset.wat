@@ -23,29 +23,29 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a1, 0x40(a0)
-;;       bltu    a2, a1, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a1, 0x38(a0)
-;;       add     a1, a1, a2
-;;       sb      a3, 0(a1)
+;;       ld      t0, 0x40(a0)
+;;       bgeu    a2, t0, 0x20
+;;       ld      t1, 0x38(a0)
+;;       add     t1, t1, a2
+;;       sb      a3, 0(t1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a1, 0x40(a0)
-;;       bltu    a2, a1, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a1, 0x38(a0)
-;;       add     a1, a1, a2
-;;       lbu     a0, 0(a1)
+;;       ld      t0, 0x40(a0)
+;;       bgeu    a2, t0, 0x20
+;;       ld      t1, 0x38(a0)
+;;       add     t1, t1, a2
+;;       lbu     a0, 0(t1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
@@ -23,14 +23,13 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a4, 0x40(a0)
-;;       ld      a5, 0x38(a0)
-;;       sltu    a0, a4, a2
-;;       add     a4, a5, a2
-;;       neg     a2, a0
-;;       not     a5, a2
-;;       and     a0, a4, a5
-;;       sw      a3, 0(a0)
+;;       ld      a6, 0x40(a0)
+;;       ld      a7, 0x38(a0)
+;;       add     a7, a7, a2
+;;       sltu    t1, a6, a2
+;;       addi    a6, t1, -1
+;;       and     t3, a7, a6
+;;       sw      a3, 0(t3)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -41,14 +40,13 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a4, 0x40(a0)
-;;       ld      a5, 0x38(a0)
-;;       sltu    a0, a4, a2
-;;       add     a4, a5, a2
-;;       neg     a2, a0
-;;       not     a5, a2
-;;       and     a0, a4, a5
-;;       lw      a0, 0(a0)
+;;       ld      a6, 0x40(a0)
+;;       ld      a7, 0x38(a0)
+;;       add     a7, a7, a2
+;;       sltu    t1, a6, a2
+;;       addi    a6, t1, -1
+;;       and     t3, a7, a6
+;;       lw      a0, 0(t3)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -23,17 +23,16 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a1, 0x40(a0)
-;;       ld      a4, 0x38(a0)
-;;       sltu    a1, a1, a2
-;;       add     a2, a4, a2
-;;       lui     a0, 0xffff
-;;       slli    a4, a0, 4
-;;       add     a2, a2, a4
-;;       neg     a5, a1
-;;       not     a1, a5
-;;       and     a4, a2, a1
-;;       sw      a3, 0(a4)
+;;       ld      t4, 0x40(a0)
+;;       ld      t5, 0x38(a0)
+;;       add     t5, t5, a2
+;;       lui     t3, 0xffff
+;;       slli    a0, t3, 4
+;;       add     t5, t5, a0
+;;       sltu    a7, t4, a2
+;;       addi    t4, a7, -1
+;;       and     a0, t5, t4
+;;       sw      a3, 0(a0)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -44,17 +43,16 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a1, 0x40(a0)
-;;       ld      a3, 0x38(a0)
-;;       sltu    a1, a1, a2
-;;       add     a2, a3, a2
-;;       lui     a0, 0xffff
-;;       slli    a3, a0, 4
-;;       add     a2, a2, a3
-;;       neg     a5, a1
-;;       not     a1, a5
-;;       and     a3, a2, a1
-;;       lw      a0, 0(a3)
+;;       ld      t4, 0x40(a0)
+;;       ld      t5, 0x38(a0)
+;;       add     t5, t5, a2
+;;       lui     t3, 0xffff
+;;       slli    a0, t3, 4
+;;       add     t5, t5, a0
+;;       sltu    a7, t4, a2
+;;       addi    t4, a7, -1
+;;       and     a0, t5, t4
+;;       lw      a0, 0(a0)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -23,15 +23,13 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a1, 0x40(a0)
-;;       ld      a5, 0x38(a0)
-;;       sltu    a4, a2, a1
-;;       xori    a0, a4, 1
-;;       add     a5, a5, a2
-;;       neg     a4, a0
-;;       not     a0, a4
-;;       and     a1, a5, a0
-;;       sb      a3, 0(a1)
+;;       ld      a6, 0x40(a0)
+;;       ld      a7, 0x38(a0)
+;;       add     a7, a7, a2
+;;       sltu    t1, a2, a6
+;;       neg     a6, t1
+;;       and     t3, a7, a6
+;;       sb      a3, 0(t3)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -42,15 +40,13 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a1, 0x40(a0)
-;;       ld      a5, 0x38(a0)
-;;       sltu    a4, a2, a1
-;;       xori    a0, a4, 1
-;;       add     a5, a5, a2
-;;       neg     a3, a0
-;;       not     a0, a3
-;;       and     a1, a5, a0
-;;       lbu     a0, 0(a1)
+;;       ld      a6, 0x40(a0)
+;;       ld      a7, 0x38(a0)
+;;       add     a7, a7, a2
+;;       sltu    t1, a2, a6
+;;       neg     a6, t1
+;;       and     t3, a7, a6
+;;       lbu     a0, 0(t3)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -23,16 +23,15 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a4, 0x40(a0)
-;;       ld      a1, 0x38(a0)
-;;       sltu    a0, a4, a2
-;;       add     a1, a1, a2
-;;       lui     a2, 1
-;;       add     a1, a1, a2
-;;       neg     a4, a0
-;;       not     a0, a4
-;;       and     a2, a1, a0
-;;       sb      a3, 0(a2)
+;;       ld      t3, 0x40(a0)
+;;       ld      t4, 0x38(a0)
+;;       add     t4, t4, a2
+;;       lui     a7, 1
+;;       add     t4, t4, a7
+;;       sltu    a6, t3, a2
+;;       addi    t3, a6, -1
+;;       and     t5, t4, t3
+;;       sb      a3, 0(t5)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -43,16 +42,15 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a3, 0x40(a0)
-;;       ld      a1, 0x38(a0)
-;;       sltu    a0, a3, a2
-;;       add     a1, a1, a2
-;;       lui     a2, 1
-;;       add     a1, a1, a2
-;;       neg     a4, a0
-;;       not     a0, a4
-;;       and     a2, a1, a0
-;;       lbu     a0, 0(a2)
+;;       ld      t3, 0x40(a0)
+;;       ld      t4, 0x38(a0)
+;;       add     t4, t4, a2
+;;       lui     a7, 1
+;;       add     t4, t4, a7
+;;       sltu    a6, t3, a2
+;;       addi    t3, a6, -1
+;;       and     t5, t4, t3
+;;       lbu     a0, 0(t5)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -23,37 +23,37 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       slli    a4, a2, 0x20
-;;       srli    a5, a4, 0x20
-;;       lui     a2, 0x40000
-;;       addi    a4, a2, -1
-;;       slli    a1, a4, 2
-;;       bgeu    a1, a5, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a0, 0x38(a0)
-;;       add     a5, a0, a5
-;;       sw      a3, 0(a5)
+;;       slli    a6, a2, 0x20
+;;       srli    t3, a6, 0x20
+;;       addi    t2, zero, 1
+;;       slli    a7, t2, 0x20
+;;       addi    t4, a7, -4
+;;       bltu    t4, t3, 0x20
+;;       ld      t4, 0x38(a0)
+;;       add     t3, t4, t3
+;;       sw      a3, 0(t3)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       slli    a3, a2, 0x20
-;;       srli    a5, a3, 0x20
-;;       lui     a2, 0x40000
-;;       addi    a4, a2, -1
-;;       slli    a1, a4, 2
-;;       bgeu    a1, a5, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a0, 0x38(a0)
-;;       add     a5, a0, a5
-;;       lw      a0, 0(a5)
+;;       slli    a6, a2, 0x20
+;;       srli    t3, a6, 0x20
+;;       addi    t2, zero, 1
+;;       slli    a7, t2, 0x20
+;;       addi    t4, a7, -4
+;;       bltu    t4, t3, 0x20
+;;       ld      t4, 0x38(a0)
+;;       add     t3, t4, t3
+;;       lw      a0, 0(t3)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
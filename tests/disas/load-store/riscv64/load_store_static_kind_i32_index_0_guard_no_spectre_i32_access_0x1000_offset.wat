@@ -23,41 +23,41 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       slli    a4, a2, 0x20
-;;       srli    a5, a4, 0x20
-;;       lui     a2, 0x40000
-;;       addi    a4, a2, -0x401
-;;       slli    a1, a4, 2
-;;       bgeu    a1, a5, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
+;;       slli    t3, a2, 0x20
+;;       srli    t5, t3, 0x20
+;;       lui     a7, 0x40000
+;;       addi    t4, a7, -0x401
+;;       slli    a1, t4, 2
+;;       bltu    a1, t5, 0x28
 ;;       ld      a0, 0x38(a0)
-;;       add     a5, a0, a5
-;;       lui     t6, 1
-;;       add     t6, t6, a5
-;;       sw      a3, 0(t6)
+;;       add     t5, a0, t5
+;;       lui     t4, 1
+;;       add     a0, t5, t4
+;;       sw      a3, 0(a0)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       slli    a3, a2, 0x20
-;;       srli    a5, a3, 0x20
-;;       lui     a2, 0x40000
-;;       addi    a4, a2, -0x401
-;;       slli    a1, a4, 2
-;;       bgeu    a1, a5, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
+;;       slli    t3, a2, 0x20
+;;       srli    t5, t3, 0x20
+;;       lui     a7, 0x40000
+;;       addi    t4, a7, -0x401
+;;       slli    a1, t4, 2
+;;       bltu    a1, t5, 0x28
 ;;       ld      a0, 0x38(a0)
-;;       add     a5, a0, a5
-;;       lui     t6, 1
-;;       add     t6, t6, a5
-;;       lw      a0, 0(t6)
+;;       add     t5, a0, t5
+;;       lui     t4, 1
+;;       add     a0, t5, t4
+;;       lw      a0, 0(a0)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
@@ -23,45 +23,41 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       slli    a1, a2, 0x20
-;;       srli    a4, a1, 0x20
-;;       auipc   a2, 0
-;;       ld      a2, 0x38(a2)
-;;       bgeu    a2, a4, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a5, 0x38(a0)
-;;       add     a4, a5, a4
-;;       lui     t6, 1
-;;       add     t6, t6, a4
-;;       sb      a3, 0(t6)
+;;       slli    t3, a2, 0x20
+;;       srli    t5, t3, 0x20
+;;       lui     a7, 0xfefff
+;;       slli    t4, a7, 0x14
+;;       srli    a1, t4, 0x20
+;;       bltu    a1, t5, 0x28
+;;       ld      a0, 0x38(a0)
+;;       add     t5, a0, t5
+;;       lui     t4, 1
+;;       add     a0, t5, t4
+;;       sb      a3, 0(a0)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
 ;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       .byte   0xff, 0xef, 0xff, 0xff
-;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       slli    a1, a2, 0x20
-;;       srli    a3, a1, 0x20
-;;       auipc   a2, 0
-;;       ld      a2, 0x38(a2)
-;;       bgeu    a2, a3, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a4, 0x38(a0)
-;;       add     a3, a4, a3
-;;       lui     t6, 1
-;;       add     t6, t6, a3
-;;       lbu     a0, 0(t6)
+;;       slli    t3, a2, 0x20
+;;       srli    t5, t3, 0x20
+;;       lui     a7, 0xfefff
+;;       slli    t4, a7, 0x14
+;;       srli    a1, t4, 0x20
+;;       bltu    a1, t5, 0x28
+;;       ld      a0, 0x38(a0)
+;;       add     t5, a0, t5
+;;       lui     t4, 1
+;;       add     a0, t5, t4
+;;       lbu     a0, 0(a0)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
 ;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       .byte   0xff, 0xef, 0xff, 0xff
-;;       .byte   0x00, 0x00, 0x00, 0x00
@@ -23,41 +23,41 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       slli    a5, a2, 0x20
-;;       srli    a1, a5, 0x20
-;;       lui     a5, 0x10
-;;       addi    a2, a5, -1
-;;       bgeu    a2, a1, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a2, 0x38(a0)
-;;       add     a1, a2, a1
-;;       lui     a0, 0xffff
-;;       slli    a2, a0, 4
-;;       add     a1, a1, a2
-;;       sb      a3, 0(a1)
+;;       slli    t3, a2, 0x20
+;;       srli    t5, t3, 0x20
+;;       lui     t3, 0x10
+;;       addi    a1, t3, -1
+;;       bltu    a1, t5, 0x2c
+;;       ld      a0, 0x38(a0)
+;;       add     t5, a0, t5
+;;       lui     t4, 0xffff
+;;       slli    a0, t4, 4
+;;       add     t5, t5, a0
+;;       sb      a3, 0(t5)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       slli    a5, a2, 0x20
-;;       srli    a1, a5, 0x20
-;;       lui     a5, 0x10
-;;       addi    a2, a5, -1
-;;       bgeu    a2, a1, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a2, 0x38(a0)
-;;       add     a1, a2, a1
-;;       lui     a0, 0xffff
-;;       slli    a2, a0, 4
-;;       add     a1, a1, a2
-;;       lbu     a0, 0(a1)
+;;       slli    t3, a2, 0x20
+;;       srli    t5, t3, 0x20
+;;       lui     t3, 0x10
+;;       addi    a1, t3, -1
+;;       bltu    a1, t5, 0x2c
+;;       ld      a0, 0x38(a0)
+;;       add     t5, a0, t5
+;;       lui     t4, 0xffff
+;;       slli    a0, t4, 4
+;;       add     t5, t5, a0
+;;       lbu     a0, 0(t5)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
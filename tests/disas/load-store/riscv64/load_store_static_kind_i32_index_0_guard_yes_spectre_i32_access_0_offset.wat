@@ -23,18 +23,18 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       slli    a1, a2, 0x20
-;;       srli    a4, a1, 0x20
-;;       lui     a1, 0x40000
-;;       addi    a2, a1, -1
-;;       slli    a5, a2, 2
-;;       sltu    a2, a5, a4
-;;       ld      a5, 0x38(a0)
-;;       add     a4, a5, a4
-;;       neg     a0, a2
-;;       not     a2, a0
-;;       and     a4, a4, a2
-;;       sw      a3, 0(a4)
+;;       mv      a5, a0
+;;       slli    t4, a2, 0x20
+;;       srli    a0, t4, 0x20
+;;       addi    t3, zero, 1
+;;       slli    t5, t3, 0x20
+;;       addi    a1, t5, -4
+;;       ld      t5, 0x38(a5)
+;;       add     t5, t5, a0
+;;       sltu    t3, a1, a0
+;;       addi    a0, t3, -1
+;;       and     a1, t5, a0
+;;       sw      a3, 0(a1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -45,18 +45,18 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       slli    a1, a2, 0x20
-;;       srli    a3, a1, 0x20
-;;       lui     a1, 0x40000
-;;       addi    a2, a1, -1
-;;       slli    a4, a2, 2
-;;       sltu    a2, a4, a3
-;;       ld      a4, 0x38(a0)
-;;       add     a3, a4, a3
-;;       neg     a0, a2
-;;       not     a2, a0
-;;       and     a4, a3, a2
-;;       lw      a0, 0(a4)
+;;       mv      a5, a0
+;;       slli    t4, a2, 0x20
+;;       srli    a0, t4, 0x20
+;;       addi    t3, zero, 1
+;;       slli    t5, t3, 0x20
+;;       addi    a1, t5, -4
+;;       ld      t5, 0x38(a5)
+;;       add     t5, t5, a0
+;;       sltu    t3, a1, a0
+;;       addi    a0, t3, -1
+;;       and     a1, t5, a0
+;;       lw      a0, 0(a1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
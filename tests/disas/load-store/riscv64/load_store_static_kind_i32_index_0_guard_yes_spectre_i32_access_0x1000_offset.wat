@@ -23,20 +23,19 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       slli    a4, a2, 0x20
-;;       srli    a5, a4, 0x20
-;;       lui     a2, 0x40000
-;;       addi    a4, a2, -0x401
-;;       slli    a1, a4, 2
-;;       sltu    a4, a1, a5
-;;       ld      a0, 0x38(a0)
-;;       add     a5, a0, a5
+;;       slli    a1, a2, 0x20
+;;       srli    a2, a1, 0x20
+;;       lui     t5, 0x40000
+;;       addi    a1, t5, -0x401
+;;       slli    a4, a1, 2
+;;       ld      a1, 0x38(a0)
+;;       add     a1, a1, a2
 ;;       lui     a0, 1
-;;       add     a5, a5, a0
-;;       neg     a2, a4
-;;       not     a4, a2
-;;       and     a0, a5, a4
-;;       sw      a3, 0(a0)
+;;       add     a5, a1, a0
+;;       sltu    t5, a4, a2
+;;       addi    a1, t5, -1
+;;       and     a4, a5, a1
+;;       sw      a3, 0(a4)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -47,20 +46,19 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       slli    a3, a2, 0x20
-;;       srli    a5, a3, 0x20
-;;       lui     a2, 0x40000
-;;       addi    a4, a2, -0x401
-;;       slli    a1, a4, 2
-;;       sltu    a4, a1, a5
-;;       ld      a0, 0x38(a0)
-;;       add     a5, a0, a5
+;;       slli    a1, a2, 0x20
+;;       srli    a2, a1, 0x20
+;;       lui     t5, 0x40000
+;;       addi    a1, t5, -0x401
+;;       slli    a3, a1, 2
+;;       ld      a1, 0x38(a0)
+;;       add     a1, a1, a2
 ;;       lui     a0, 1
-;;       add     a5, a5, a0
-;;       neg     a2, a4
-;;       not     a4, a2
-;;       and     a0, a5, a4
-;;       lw      a0, 0(a0)
+;;       add     a4, a1, a0
+;;       sltu    t5, a3, a2
+;;       addi    a1, t5, -1
+;;       and     a3, a4, a1
+;;       lw      a0, 0(a3)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -23,20 +23,19 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       slli    a4, a2, 0x20
-;;       srli    a5, a4, 0x20
-;;       lui     a4, 0x10
-;;       addi    a1, a4, -4
-;;       sltu    a4, a1, a5
-;;       ld      a0, 0x38(a0)
-;;       add     a5, a0, a5
+;;       slli    a1, a2, 0x20
+;;       srli    a2, a1, 0x20
+;;       lui     a1, 0x10
+;;       addi    a4, a1, -4
+;;       ld      a1, 0x38(a0)
+;;       add     a1, a1, a2
 ;;       lui     a0, 0xffff
-;;       slli    a0, a0, 4
-;;       add     a5, a5, a0
-;;       neg     a2, a4
-;;       not     a4, a2
-;;       and     a0, a5, a4
-;;       sw      a3, 0(a0)
+;;       slli    a5, a0, 4
+;;       add     a1, a1, a5
+;;       sltu    t5, a4, a2
+;;       addi    a2, t5, -1
+;;       and     a4, a1, a2
+;;       sw      a3, 0(a4)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -47,20 +46,19 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       slli    a3, a2, 0x20
-;;       srli    a5, a3, 0x20
-;;       lui     a3, 0x10
-;;       addi    a1, a3, -4
-;;       sltu    a4, a1, a5
-;;       ld      a0, 0x38(a0)
-;;       add     a5, a0, a5
-;;       lui     a3, 0xffff
-;;       slli    a0, a3, 4
-;;       add     a5, a5, a0
-;;       neg     a2, a4
-;;       not     a4, a2
-;;       and     a0, a5, a4
-;;       lw      a0, 0(a0)
+;;       slli    a1, a2, 0x20
+;;       srli    a2, a1, 0x20
+;;       lui     a1, 0x10
+;;       addi    a3, a1, -4
+;;       ld      a1, 0x38(a0)
+;;       add     a1, a1, a2
+;;       lui     a0, 0xffff
+;;       slli    a4, a0, 4
+;;       add     a1, a1, a4
+;;       sltu    t5, a3, a2
+;;       addi    a2, t5, -1
+;;       and     a3, a1, a2
+;;       lw      a0, 0(a3)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -24,25 +24,22 @@
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
 ;;       slli    a1, a2, 0x20
+;;       srli    a2, a1, 0x20
+;;       lui     t5, 0xfefff
+;;       slli    a1, t5, 0x14
 ;;       srli    a4, a1, 0x20
-;;       auipc   a2, 0
-;;       ld      a2, 0x40(a2)
-;;       sltu    a2, a2, a4
-;;       ld      a5, 0x38(a0)
-;;       add     a4, a5, a4
-;;       lui     a5, 1
-;;       add     a4, a4, a5
-;;       neg     a0, a2
-;;       not     a2, a0
-;;       and     a4, a4, a2
+;;       ld      a1, 0x38(a0)
+;;       add     a1, a1, a2
+;;       lui     a0, 1
+;;       add     a5, a1, a0
+;;       sltu    t5, a4, a2
+;;       addi    a1, t5, -1
+;;       and     a4, a5, a1
 ;;       sb      a3, 0(a4)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       .byte   0xff, 0xef, 0xff, 0xff
-;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
@@ -50,22 +47,19 @@
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
 ;;       slli    a1, a2, 0x20
+;;       srli    a2, a1, 0x20
+;;       lui     t5, 0xfefff
+;;       slli    a1, t5, 0x14
 ;;       srli    a3, a1, 0x20
-;;       auipc   a2, 0
-;;       ld      a2, 0x40(a2)
-;;       sltu    a2, a2, a3
-;;       ld      a4, 0x38(a0)
-;;       add     a3, a4, a3
-;;       lui     a4, 1
-;;       add     a3, a3, a4
-;;       neg     a0, a2
-;;       not     a2, a0
-;;       and     a4, a3, a2
-;;       lbu     a0, 0(a4)
+;;       ld      a1, 0x38(a0)
+;;       add     a1, a1, a2
+;;       lui     a0, 1
+;;       add     a4, a1, a0
+;;       sltu    t5, a3, a2
+;;       addi    a1, t5, -1
+;;       and     a3, a4, a1
+;;       lbu     a0, 0(a3)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       .byte   0xff, 0xef, 0xff, 0xff
-;;       .byte   0x00, 0x00, 0x00, 0x00
@@ -23,11 +23,11 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a1, 0x38(a0)
-;;       slli    a0, a2, 0x20
-;;       srli    a2, a0, 0x20
-;;       add     a1, a1, a2
-;;       sb      a3, 0(a1)
+;;       ld      t1, 0x38(a0)
+;;       slli    t0, a2, 0x20
+;;       srli    t2, t0, 0x20
+;;       add     t1, t1, t2
+;;       sb      a3, 0(t1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -38,11 +38,11 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a1, 0x38(a0)
-;;       slli    a0, a2, 0x20
-;;       srli    a2, a0, 0x20
-;;       add     a1, a1, a2
-;;       lbu     a0, 0(a1)
+;;       ld      t1, 0x38(a0)
+;;       slli    t0, a2, 0x20
+;;       srli    t2, t0, 0x20
+;;       add     t1, t1, t2
+;;       lbu     a0, 0(t1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
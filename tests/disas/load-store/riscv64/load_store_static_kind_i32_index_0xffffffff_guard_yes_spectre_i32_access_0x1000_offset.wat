@@ -23,13 +23,13 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a1, 0x38(a0)
-;;       slli    a0, a2, 0x20
-;;       srli    a2, a0, 0x20
-;;       add     a1, a1, a2
-;;       lui     t6, 1
-;;       add     t6, t6, a1
-;;       sw      a3, 0(t6)
+;;       ld      a6, 0x38(a0)
+;;       slli    t2, a2, 0x20
+;;       srli    a7, t2, 0x20
+;;       add     a6, a6, a7
+;;       lui     t2, 1
+;;       add     a7, a6, t2
+;;       sw      a3, 0(a7)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -40,13 +40,13 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a1, 0x38(a0)
-;;       slli    a0, a2, 0x20
-;;       srli    a2, a0, 0x20
-;;       add     a1, a1, a2
-;;       lui     t6, 1
-;;       add     t6, t6, a1
-;;       lw      a0, 0(t6)
+;;       ld      a6, 0x38(a0)
+;;       slli    t2, a2, 0x20
+;;       srli    a7, t2, 0x20
+;;       add     a6, a6, a7
+;;       lui     t2, 1
+;;       add     a7, a6, t2
+;;       lw      a0, 0(a7)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
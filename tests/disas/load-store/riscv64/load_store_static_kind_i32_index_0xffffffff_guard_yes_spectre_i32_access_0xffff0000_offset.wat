@@ -23,14 +23,14 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a4, 0x38(a0)
-;;       slli    a5, a2, 0x20
-;;       srli    a5, a5, 0x20
-;;       add     a4, a4, a5
-;;       lui     a5, 0xffff
-;;       slli    a5, a5, 4
-;;       add     a4, a4, a5
-;;       sw      a3, 0(a4)
+;;       ld      a7, 0x38(a0)
+;;       slli    a6, a2, 0x20
+;;       srli    t3, a6, 0x20
+;;       add     a7, a7, t3
+;;       lui     a6, 0xffff
+;;       slli    t3, a6, 4
+;;       add     a7, a7, t3
+;;       sw      a3, 0(a7)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -41,14 +41,14 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       ld      a4, 0x38(a0)
-;;       slli    a3, a2, 0x20
-;;       srli    a5, a3, 0x20
-;;       add     a4, a4, a5
-;;       lui     a3, 0xffff
-;;       slli    a5, a3, 4
-;;       add     a4, a4, a5
-;;       lw      a0, 0(a4)
+;;       ld      a7, 0x38(a0)
+;;       slli    a6, a2, 0x20
+;;       srli    t3, a6, 0x20
+;;       add     a7, a7, t3
+;;       lui     a6, 0xffff
+;;       slli    t3, a6, 4
+;;       add     a7, a7, t3
+;;       lw      a0, 0(a7)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -23,33 +23,33 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       lui     a1, 0x40000
-;;       addi    a4, a1, -1
-;;       slli    a4, a4, 2
-;;       bgeu    a4, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a4, 0x38(a0)
-;;       add     a4, a4, a2
-;;       sw      a3, 0(a4)
+;;       addi    t0, zero, 1
+;;       slli    t2, t0, 0x20
+;;       addi    a7, t2, -4
+;;       bltu    a7, a2, 0x20
+;;       ld      a6, 0x38(a0)
+;;       add     a6, a6, a2
+;;       sw      a3, 0(a6)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       lui     a1, 0x40000
-;;       addi    a3, a1, -1
-;;       slli    a4, a3, 2
-;;       bgeu    a4, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a3, 0x38(a0)
-;;       add     a3, a3, a2
-;;       lw      a0, 0(a3)
+;;       addi    t0, zero, 1
+;;       slli    t2, t0, 0x20
+;;       addi    a7, t2, -4
+;;       bltu    a7, a2, 0x20
+;;       ld      a6, 0x38(a0)
+;;       add     a6, a6, a2
+;;       lw      a0, 0(a6)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
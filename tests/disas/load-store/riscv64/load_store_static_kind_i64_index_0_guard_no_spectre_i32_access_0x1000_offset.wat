@@ -23,37 +23,37 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       lui     a1, 0x40000
-;;       addi    a4, a1, -0x401
-;;       slli    a4, a4, 2
-;;       bgeu    a4, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a4, 0x38(a0)
-;;       add     a4, a4, a2
-;;       lui     t6, 1
-;;       add     t6, t6, a4
-;;       sw      a3, 0(t6)
+;;       lui     t2, 0x40000
+;;       addi    a7, t2, -0x401
+;;       slli    t4, a7, 2
+;;       bltu    t4, a2, 0x28
+;;       ld      t3, 0x38(a0)
+;;       add     t3, t3, a2
+;;       lui     a7, 1
+;;       add     t4, t3, a7
+;;       sw      a3, 0(t4)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       lui     a1, 0x40000
-;;       addi    a3, a1, -0x401
-;;       slli    a4, a3, 2
-;;       bgeu    a4, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a3, 0x38(a0)
-;;       add     a3, a3, a2
-;;       lui     t6, 1
-;;       add     t6, t6, a3
-;;       lw      a0, 0(t6)
+;;       lui     t2, 0x40000
+;;       addi    a7, t2, -0x401
+;;       slli    t4, a7, 2
+;;       bltu    t4, a2, 0x28
+;;       ld      t3, 0x38(a0)
+;;       add     t3, t3, a2
+;;       lui     a7, 1
+;;       add     t4, t3, a7
+;;       lw      a0, 0(t4)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
@@ -23,37 +23,31 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       auipc   a1, 0
-;;       ld      a1, 0x30(a1)
-;;       bgeu    a1, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a1, 0x38(a0)
-;;       add     a1, a1, a2
-;;       sb      a3, 0(a1)
+;;       addi    t0, zero, -1
+;;       srli    t2, t0, 0x20
+;;       bltu    t2, a2, 0x20
+;;       ld      t2, 0x38(a0)
+;;       add     t2, t2, a2
+;;       sb      a3, 0(t2)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
 ;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       .byte   0xff, 0xff, 0xff, 0xff
-;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       auipc   a1, 0
-;;       ld      a1, 0x30(a1)
-;;       bgeu    a1, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a1, 0x38(a0)
-;;       add     a1, a1, a2
-;;       lbu     a0, 0(a1)
+;;       addi    t0, zero, -1
+;;       srli    t2, t0, 0x20
+;;       bltu    t2, a2, 0x20
+;;       ld      t2, 0x38(a0)
+;;       add     t2, t2, a2
+;;       lbu     a0, 0(t2)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
 ;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       .byte   0xff, 0xff, 0xff, 0xff
-;;       .byte   0x00, 0x00, 0x00, 0x00
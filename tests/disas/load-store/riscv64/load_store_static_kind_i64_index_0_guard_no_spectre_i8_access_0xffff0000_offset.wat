@@ -23,37 +23,37 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       lui     a4, 0x10
-;;       addi    a5, a4, -1
-;;       bgeu    a5, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a5, 0x38(a0)
-;;       add     a5, a5, a2
-;;       lui     a4, 0xffff
-;;       slli    a0, a4, 4
-;;       add     a5, a5, a0
-;;       sb      a3, 0(a5)
+;;       lui     a6, 0x10
+;;       addi    t3, a6, -1
+;;       bltu    t3, a2, 0x2c
+;;       ld      t3, 0x38(a0)
+;;       add     t3, t3, a2
+;;       lui     a7, 0xffff
+;;       slli    t4, a7, 4
+;;       add     t3, t3, t4
+;;       sb      a3, 0(t3)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       lui     a3, 0x10
-;;       addi    a5, a3, -1
-;;       bgeu    a5, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a5, 0x38(a0)
-;;       add     a5, a5, a2
-;;       lui     a4, 0xffff
-;;       slli    a0, a4, 4
-;;       add     a5, a5, a0
-;;       lbu     a0, 0(a5)
+;;       lui     a6, 0x10
+;;       addi    t3, a6, -1
+;;       bltu    t3, a2, 0x2c
+;;       ld      t3, 0x38(a0)
+;;       add     t3, t3, a2
+;;       lui     a7, 0xffff
+;;       slli    t4, a7, 4
+;;       add     t3, t3, t4
+;;       lbu     a0, 0(t3)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
+;;       .byte   0x00, 0x00, 0x00, 0x00
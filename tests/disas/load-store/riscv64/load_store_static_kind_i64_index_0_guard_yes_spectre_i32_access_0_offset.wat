@@ -23,16 +23,15 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       lui     a4, 0x40000
-;;       addi    a1, a4, -1
-;;       slli    a4, a1, 2
-;;       sltu    a1, a4, a2
-;;       ld      a0, 0x38(a0)
-;;       add     a0, a0, a2
-;;       neg     a4, a1
-;;       not     a1, a4
-;;       and     a2, a0, a1
-;;       sw      a3, 0(a2)
+;;       addi    a6, zero, 1
+;;       slli    t3, a6, 0x20
+;;       addi    t5, t3, -4
+;;       ld      t3, 0x38(a0)
+;;       add     t3, t3, a2
+;;       sltu    a6, t5, a2
+;;       addi    t4, a6, -1
+;;       and     t5, t3, t4
+;;       sw      a3, 0(t5)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -43,16 +42,15 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       lui     a4, 0x40000
-;;       addi    a1, a4, -1
-;;       slli    a3, a1, 2
-;;       sltu    a1, a3, a2
-;;       ld      a0, 0x38(a0)
-;;       add     a0, a0, a2
-;;       neg     a4, a1
-;;       not     a1, a4
-;;       and     a2, a0, a1
-;;       lw      a0, 0(a2)
+;;       addi    a6, zero, 1
+;;       slli    t3, a6, 0x20
+;;       addi    t5, t3, -4
+;;       ld      t3, 0x38(a0)
+;;       add     t3, t3, a2
+;;       sltu    a6, t5, a2
+;;       addi    t4, a6, -1
+;;       and     t5, t3, t4
+;;       lw      a0, 0(t5)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
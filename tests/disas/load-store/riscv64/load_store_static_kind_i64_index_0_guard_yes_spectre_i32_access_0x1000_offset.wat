@@ -23,18 +23,17 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       lui     a1, 0x40000
-;;       addi    a4, a1, -0x401
-;;       slli    a4, a4, 2
-;;       sltu    a4, a4, a2
-;;       ld      a5, 0x38(a0)
-;;       add     a2, a5, a2
-;;       lui     a5, 1
-;;       add     a2, a2, a5
-;;       neg     a0, a4
-;;       not     a4, a0
-;;       and     a4, a2, a4
-;;       sw      a3, 0(a4)
+;;       lui     t3, 0x40000
+;;       addi    t5, t3, -0x401
+;;       slli    a1, t5, 2
+;;       ld      t5, 0x38(a0)
+;;       add     t5, t5, a2
+;;       lui     t4, 1
+;;       add     a0, t5, t4
+;;       sltu    t3, a1, a2
+;;       addi    t5, t3, -1
+;;       and     a1, a0, t5
+;;       sw      a3, 0(a1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -45,18 +44,17 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       lui     a1, 0x40000
-;;       addi    a3, a1, -0x401
-;;       slli    a4, a3, 2
-;;       sltu    a3, a4, a2
-;;       ld      a4, 0x38(a0)
-;;       add     a2, a4, a2
-;;       lui     a4, 1
-;;       add     a2, a2, a4
-;;       neg     a0, a3
-;;       not     a3, a0
-;;       and     a4, a2, a3
-;;       lw      a0, 0(a4)
+;;       lui     t3, 0x40000
+;;       addi    t5, t3, -0x401
+;;       slli    a1, t5, 2
+;;       ld      t5, 0x38(a0)
+;;       add     t5, t5, a2
+;;       lui     t4, 1
+;;       add     a0, t5, t4
+;;       sltu    t3, a1, a2
+;;       addi    t5, t3, -1
+;;       and     a1, a0, t5
+;;       lw      a0, 0(a1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -23,41 +23,37 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       auipc   a1, 0
-;;       ld      a1, 0x38(a1)
-;;       bgeu    a1, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a1, 0x38(a0)
-;;       add     a1, a1, a2
-;;       lui     t6, 1
-;;       add     t6, t6, a1
-;;       sb      a3, 0(t6)
+;;       lui     t2, 0xfefff
+;;       slli    a7, t2, 0x14
+;;       srli    t4, a7, 0x20
+;;       bltu    t4, a2, 0x28
+;;       ld      t3, 0x38(a0)
+;;       add     t3, t3, a2
+;;       lui     a7, 1
+;;       add     t4, t3, a7
+;;       sb      a3, 0(t4)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
 ;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       .byte   0xff, 0xef, 0xff, 0xff
-;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       auipc   a1, 0
-;;       ld      a1, 0x38(a1)
-;;       bgeu    a1, a2, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a1, 0x38(a0)
-;;       add     a1, a1, a2
-;;       lui     t6, 1
-;;       add     t6, t6, a1
-;;       lbu     a0, 0(t6)
+;;       lui     t2, 0xfefff
+;;       slli    a7, t2, 0x14
+;;       srli    t4, a7, 0x20
+;;       bltu    t4, a2, 0x28
+;;       ld      t3, 0x38(a0)
+;;       add     t3, t3, a2
+;;       lui     a7, 1
+;;       add     t4, t3, a7
+;;       lbu     a0, 0(t4)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
 ;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       .byte   0xff, 0xef, 0xff, 0xff
-;;       .byte   0x00, 0x00, 0x00, 0x00
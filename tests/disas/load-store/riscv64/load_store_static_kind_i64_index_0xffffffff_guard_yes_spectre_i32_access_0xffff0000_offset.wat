@@ -23,18 +23,17 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       lui     a1, 0x10
-;;       addi    a4, a1, -4
-;;       sltu    a4, a4, a2
-;;       ld      a5, 0x38(a0)
-;;       add     a2, a5, a2
-;;       lui     a1, 0xffff
-;;       slli    a5, a1, 4
-;;       add     a2, a2, a5
-;;       neg     a0, a4
-;;       not     a4, a0
-;;       and     a4, a2, a4
-;;       sw      a3, 0(a4)
+;;       lui     t4, 0x10
+;;       addi    a1, t4, -4
+;;       ld      t5, 0x38(a0)
+;;       add     t5, t5, a2
+;;       lui     t4, 0xffff
+;;       slli    a0, t4, 4
+;;       add     t5, t5, a0
+;;       sltu    t3, a1, a2
+;;       addi    a0, t3, -1
+;;       and     a1, t5, a0
+;;       sw      a3, 0(a1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
@@ -45,18 +44,17 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       lui     a1, 0x10
-;;       addi    a3, a1, -4
-;;       sltu    a3, a3, a2
-;;       ld      a4, 0x38(a0)
-;;       add     a2, a4, a2
-;;       lui     a1, 0xffff
-;;       slli    a4, a1, 4
-;;       add     a2, a2, a4
-;;       neg     a0, a3
-;;       not     a3, a0
-;;       and     a4, a2, a3
-;;       lw      a0, 0(a4)
+;;       lui     t4, 0x10
+;;       addi    a1, t4, -4
+;;       ld      t5, 0x38(a0)
+;;       add     t5, t5, a2
+;;       lui     t4, 0xffff
+;;       slli    a0, t4, 4
+;;       add     t5, t5, a0
+;;       sltu    t3, a1, a2
+;;       addi    a0, t3, -1
+;;       and     a1, t5, a0
+;;       lw      a0, 0(a1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
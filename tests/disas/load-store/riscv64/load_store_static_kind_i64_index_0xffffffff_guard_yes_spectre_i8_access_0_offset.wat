@@ -23,41 +23,33 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       auipc   a4, 0
-;;       ld      a4, 0x38(a4)
-;;       sltu    a4, a4, a2
-;;       ld      a5, 0x38(a0)
-;;       add     a5, a5, a2
-;;       neg     a2, a4
-;;       not     a4, a2
-;;       and     a0, a5, a4
-;;       sb      a3, 0(a0)
+;;       addi    a6, zero, -1
+;;       srli    t3, a6, 0x20
+;;       ld      a7, 0x38(a0)
+;;       add     a7, a7, a2
+;;       sltu    t2, t3, a2
+;;       addi    t3, t2, -1
+;;       and     t4, a7, t3
+;;       sb      a3, 0(t4)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       .byte   0xff, 0xff, 0xff, 0xff
-;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       auipc   a4, 0
-;;       ld      a4, 0x38(a4)
-;;       sltu    a4, a4, a2
-;;       ld      a5, 0x38(a0)
-;;       add     a5, a5, a2
-;;       neg     a2, a4
-;;       not     a4, a2
-;;       and     a0, a5, a4
-;;       lbu     a0, 0(a0)
+;;       addi    a6, zero, -1
+;;       srli    t3, a6, 0x20
+;;       ld      a7, 0x38(a0)
+;;       add     a7, a7, a2
+;;       sltu    t2, t3, a2
+;;       addi    t3, t2, -1
+;;       and     t4, a7, t3
+;;       lbu     a0, 0(t4)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       .byte   0xff, 0xff, 0xff, 0xff
-;;       .byte   0x00, 0x00, 0x00, 0x00
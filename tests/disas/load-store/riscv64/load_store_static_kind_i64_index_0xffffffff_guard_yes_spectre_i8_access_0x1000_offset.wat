@@ -23,45 +23,39 @@
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       auipc   a1, 0
-;;       ld      a1, 0x40(a1)
-;;       sltu    a4, a1, a2
-;;       ld      a0, 0x38(a0)
-;;       add     a0, a0, a2
-;;       lui     a1, 1
-;;       add     a1, a0, a1
-;;       neg     a4, a4
-;;       not     a0, a4
-;;       and     a2, a1, a0
-;;       sb      a3, 0(a2)
+;;       lui     t3, 0xfefff
+;;       slli    t5, t3, 0x14
+;;       srli    a1, t5, 0x20
+;;       ld      t5, 0x38(a0)
+;;       add     t5, t5, a2
+;;       lui     t4, 1
+;;       add     a0, t5, t4
+;;       sltu    t3, a1, a2
+;;       addi    t5, t3, -1
+;;       and     a1, a0, t5
+;;       sb      a3, 0(a1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       .byte   0xff, 0xef, 0xff, 0xff
-;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;
 ;; wasm[0]::function[1]:
 ;;       addi    sp, sp, -0x10
 ;;       sd      ra, 8(sp)
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
-;;       auipc   a1, 0
-;;       ld      a1, 0x40(a1)
-;;       sltu    a3, a1, a2
-;;       ld      a0, 0x38(a0)
-;;       add     a0, a0, a2
-;;       lui     a1, 1
-;;       add     a1, a0, a1
-;;       neg     a4, a3
-;;       not     a0, a4
-;;       and     a2, a1, a0
-;;       lbu     a0, 0(a2)
+;;       lui     t3, 0xfefff
+;;       slli    t5, t3, 0x14
+;;       srli    a1, t5, 0x20
+;;       ld      t5, 0x38(a0)
+;;       add     t5, t5, a2
+;;       lui     t4, 1
+;;       add     a0, t5, t4
+;;       sltu    t3, a1, a2
+;;       addi    t5, t3, -1
+;;       and     a1, a0, t5
+;;       lbu     a0, 0(a1)
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       .byte   0xff, 0xef, 0xff, 0xff
-;;       .byte   0x00, 0x00, 0x00, 0x00
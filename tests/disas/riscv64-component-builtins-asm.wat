@@ -17,37 +17,37 @@
 ;;       sd      s0, 0(sp)
 ;;       mv      s0, sp
 ;;       addi    sp, sp, -0x10
-;;       sd      s1, 8(sp)
-;;       mv      s1, a1
-;;       lw      a1, 0(a0)
-;;       lui     a5, 0x706d7
-;;       addi    a3, a5, -0x9d
-;;       beq     a1, a3, 8
-;;       .byte   0x00, 0x00, 0x00, 0x00
-;;       ld      a1, 0x10(a0)
-;;       ld      a3, 0(s0)
-;;       sd      a3, 0x28(a1)
-;;       ld      a3, 8(s0)
-;;       sd      a3, 0x30(a1)
-;;       ld      a3, 8(a0)
-;;       ld      a3, 0x10(a3)
-;;       mv      a4, zero
-;;       slli    a1, a4, 0x20
+;;       sd      s10, 8(sp)
+;;       mv      s10, a1
+;;       lw      a4, 0(a0)
+;;       lui     a3, 0x706d7
+;;       addi    a5, a3, -0x9d
+;;       bne     a4, a5, 0x70
+;;       ld      a5, 0x10(a0)
+;;       ld      t0, 0(s0)
+;;       sd      t0, 0x28(a5)
+;;       ld      t0, 8(s0)
+;;       sd      t0, 0x30(a5)
+;;       ld      t1, 8(a0)
+;;       ld      t1, 0x10(t1)
+;;       mv      a5, zero
+;;       slli    a1, a5, 0x20
 ;;       srai    a1, a1, 0x20
 ;;       slli    a2, a2, 0x20
 ;;       srai    a2, a2, 0x20
-;;       jalr    a3
-;;       addi    a3, zero, -1
-;;       beq     a0, a3, 0x1c
-;;       ld      s1, 8(sp)
+;;       jalr    t1
+;;       addi    t1, zero, -1
+;;       beq     a0, t1, 0x1c
+;;       ld      s10, 8(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ld      ra, 8(sp)
 ;;       ld      s0, 0(sp)
 ;;       addi    sp, sp, 0x10
 ;;       ret
-;;       mv      a1, s1
-;;       ld      a4, 0x10(a1)
-;;       ld      a4, 0x198(a4)
+;;       mv      a1, s10
+;;       ld      t2, 0x10(a1)
+;;       ld      t2, 0x198(t2)
 ;;       mv      a0, a1
-;;       jalr    a4
+;;       jalr    t2
+;;       .byte   0x00, 0x00, 0x00, 0x00
 ;;       .byte   0x00, 0x00, 0x00, 0x00